@@ -12,8 +12,17 @@ mod environment;
 mod callable;
 mod class;
 mod instance;
+mod enumeration;
+mod list;
+mod map;
+mod pattern;
+mod traits;
+mod builtins;
+mod checker;
+mod macros;
+mod reflection;
 
-use self::{error_reporter::ErrorReporter, scanner::Scanner, parser::Parser, interpreter::Interpreter, printer::Print, resolver::Resolver};
+use self::{error_reporter::ErrorReporter, interpreter::Interpreter, printer::Print};
 
 
 pub struct Lox<'p, T: Print> {
@@ -29,24 +38,23 @@ impl<'p, T: Print> Lox<'p, T> {
         }
     }
 
+    pub fn set_optional_semicolons(&mut self, enabled: bool) {
+        self.interpreter.optional_semicolons = enabled;
+    }
+
+    pub fn set_strict(&mut self, enabled: bool) {
+        self.interpreter.strict = enabled;
+    }
+
     pub fn run(&mut self, source: &str) -> i32 {
         let error_reporter = ErrorReporter::new(
             source, self.repl_mode, self.interpreter.printer
         );
 
-        let mut scanner = Scanner::new(source,  &error_reporter);
-        
-        scanner.scan_tokens();
-        // if error_reporter.had_error.get() { return 70; } // 70: An internal software error has been detected
-
-        let parser = Parser::new(&scanner.tokens, &error_reporter);
-        let ast = parser.parse();
-        if error_reporter.had_error.get() { return 70; } // 70: An internal software error has been detected
+        let Some(ast) = self.interpreter.compile(source, &error_reporter) else {
+            return 70; // 70: An internal software error has been detected
+        };
 
-        let mut resolver  = Resolver::new(&error_reporter, &mut self.interpreter);
-        resolver.resolve(&ast);
-        if error_reporter.had_error.get() { return 70; } // 70: An internal software 
-        
         self.interpreter.interpret(
             &ast, &error_reporter
         )
@@ -0,0 +1,144 @@
+use std::{collections::HashMap, rc::Rc};
+
+use super::{callable::{Callable, NativeContext}, expr::Literals, interpreter::RuntimeError, list::List, map::Map};
+
+/// Built-in types whose values have methods, by the names used in `extend`.
+pub const TYPE_NAMES: [&str; 6] = ["Number", "String", "Bool", "List", "Map", "Tuple"];
+
+/// Name of built-in type of value, if values of the type have methods.
+pub fn type_name(value: &Literals) -> Option<&'static str> {
+    match value {
+        Literals::Number(_) => Some("Number"),
+        Literals::String(_) => Some("String"),
+        Literals::Bool(_) => Some("Bool"),
+        Literals::List(_) => Some("List"),
+        Literals::Map(_) => Some("Map"),
+        Literals::Tuple(_) => Some("Tuple"),
+        _ => None,
+    }
+}
+
+/// Kind of value as returned by `typeof`.
+pub fn type_of(value: &Literals) -> &'static str {
+    match value {
+        Literals::Number(_) => "number",
+        Literals::String(_) => "string",
+        Literals::Bool(_) => "bool",
+        Literals::Nil => "nil",
+        Literals::Function(Callable::Class(_)) => "class",
+        Literals::Function(_) => "function",
+        Literals::Instance(_) => "instance",
+        Literals::List(_) => "list",
+        Literals::Map(_) => "map",
+        Literals::Tuple(_) => "tuple",
+        Literals::Enum(_) => "enum",
+        Literals::Variant(_) => "variant",
+        Literals::Trait(_) => "trait",
+    }
+}
+
+/// Method tables of built-in types: method name -> callable taking the
+/// value it's called on as first argument. `extend` adds to these tables.
+pub type MethodTables = HashMap<&'static str, HashMap<String, Callable>>;
+
+/// Native methods every built-in type starts with.
+pub fn native_methods() -> MethodTables {
+    let mut tables: MethodTables = TYPE_NAMES.iter().map(|name| (*name, HashMap::new())).collect();
+
+    let number = tables.get_mut("Number").unwrap();
+    method(number, "abs", 0, |_ctx, args| Ok(Literals::Number(number_arg(&args[0]).abs())));
+    method(number, "floor", 0, |_ctx, args| Ok(Literals::Number(number_arg(&args[0]).floor())));
+
+    let string = tables.get_mut("String").unwrap();
+    method(string, "len", 0, |_ctx, args| {
+        Ok(Literals::Number(string_arg(&args[0]).chars().count() as f64))
+    });
+    method(string, "upper", 0, |_ctx, args| Ok(Literals::String(string_arg(&args[0]).to_uppercase())));
+    method(string, "lower", 0, |_ctx, args| Ok(Literals::String(string_arg(&args[0]).to_lowercase())));
+    method(string, "trim", 0, |_ctx, args| Ok(Literals::String(string_arg(&args[0]).trim().to_string())));
+    method(string, "contains", 1, |ctx, args| {
+        let Literals::String(part) = &args[1] else {
+            return Err(ctx.error("Argument of contains() must be a string".to_string()));
+        };
+        Ok(Literals::Bool(string_arg(&args[0]).contains(part.as_str())))
+    });
+
+    let list = tables.get_mut("List").unwrap();
+    method(list, "len", 0, |_ctx, args| {
+        Ok(Literals::Number(list_arg(&args[0]).elements.borrow().len() as f64))
+    });
+    method(list, "push", 1, |ctx, args| {
+        let list = list_arg(&args[0]);
+        if list.is_frozen() {
+            return Err(ctx.error("Can't modify frozen list".to_string()));
+        }
+        list.elements.borrow_mut().push(args[1].clone());
+        Ok(Literals::Nil)
+    });
+    method(list, "pop", 0, |ctx, args| {
+        let list = list_arg(&args[0]);
+        if list.is_frozen() {
+            return Err(ctx.error("Can't modify frozen list".to_string()));
+        }
+        let last = list.elements.borrow_mut().pop();
+        last.ok_or_else(|| ctx.error("Can't pop from empty list".to_string()))
+    });
+    method(list, "contains", 1, |_ctx, args| {
+        Ok(Literals::Bool(list_arg(&args[0]).elements.borrow().contains(&args[1])))
+    });
+
+    let map = tables.get_mut("Map").unwrap();
+    method(map, "len", 0, |_ctx, args| {
+        Ok(Literals::Number(map_arg(&args[0]).entries.borrow().len() as f64))
+    });
+    method(map, "keys", 0, |_ctx, args| {
+        let keys = map_arg(&args[0]).entries.borrow().iter().map(|(k, _)| k.clone()).collect();
+        Ok(Literals::List(List::new(keys)))
+    });
+    method(map, "values", 0, |_ctx, args| {
+        let values = map_arg(&args[0]).entries.borrow().iter().map(|(_, v)| v.clone()).collect();
+        Ok(Literals::List(List::new(values)))
+    });
+    method(map, "has", 1, |_ctx, args| {
+        Ok(Literals::Bool(map_arg(&args[0]).lookup(&args[1]).is_some()))
+    });
+
+    let tuple = tables.get_mut("Tuple").unwrap();
+    method(tuple, "len", 0, |_ctx, args| {
+        let Literals::Tuple(elements) = &args[0] else { unreachable!() };
+        Ok(Literals::Number(elements.len() as f64))
+    });
+
+    tables
+}
+
+/// Adds native method taking arity arguments besides the value it's called on.
+fn method(
+    table: &mut HashMap<String, Callable>,
+    name: &str,
+    arity: usize,
+    func: impl Fn(&mut dyn NativeContext, Vec<Literals>) -> Result<Literals, RuntimeError> + 'static,
+) {
+    table.insert(name.to_string(), Callable::new_native_fn(Rc::new(func), arity, name.to_string()));
+}
+
+// Methods are only looked up in the table of their receiver's type.
+fn number_arg(value: &Literals) -> f64 {
+    let Literals::Number(n) = value else { unreachable!() };
+    *n
+}
+
+fn string_arg(value: &Literals) -> &str {
+    let Literals::String(s) = value else { unreachable!() };
+    s
+}
+
+fn list_arg(value: &Literals) -> &List {
+    let Literals::List(list) = value else { unreachable!() };
+    list
+}
+
+fn map_arg(value: &Literals) -> &Map {
+    let Literals::Map(map) = value else { unreachable!() };
+    map
+}
@@ -1,15 +1,17 @@
 use std::{fmt::Debug, rc::Rc, cell::{Cell, RefCell}};
 
-use super::{expr::Literals, interpreter::{RuntimeError, Interpreter, self}, stmt::Stmt, printer::Print, environment::Scope, class::Class, instance::Instance};
+use super::{expr::Literals, interpreter::{Flow, RuntimeError, Interpreter}, stmt::Stmt, printer::Print, environment::Scope, class::Class, instance::Instance, enumeration::Variant, token::Token};
 
 thread_local!{ 
-    pub static FUNCTION_ID: Cell<usize> = Cell::new(1);
+    pub static FUNCTION_ID: Cell<usize> = const { Cell::new(1) };
 }
 #[derive(Debug, Clone, PartialEq)]
 pub enum Callable {
     Native(NativeFn),
     Foreign(ForeignFn),
-    Class(ClassInitializer)
+    Class(ClassInitializer),
+    Variant(VariantConstructor),
+    Decorated(DecoratedFn)
 }
 
 impl Callable {
@@ -17,14 +19,36 @@ impl Callable {
         Self::Native(NativeFn::new(func, arity, name, Self::get_inc_func_id()))
     }
 
-    pub fn new_foreign_fn(declaration_stmt: Rc<Stmt>, name: String, arity: usize, closure: Rc<RefCell<Scope>>) -> Self {
-        Self::Foreign(ForeignFn::new( declaration_stmt, name, arity, Self::get_inc_func_id(), closure))
+    pub fn new_foreign_fn(
+        declaration_stmt: Rc<Stmt>,
+        name: String,
+        arity: usize,
+        closure: Rc<RefCell<Scope>>,
+        class: Option<Rc<Token>>
+    ) -> Self {
+        let mut function = ForeignFn::new( declaration_stmt, name, arity, Self::get_inc_func_id(), closure);
+        function.class = class;
+        Self::Foreign(function)
     }
 
     pub fn new_class_initializer(class: Rc<Class>) -> Self {
         Self::Class(ClassInitializer::new(class, Self::get_inc_func_id()))
     }
 
+    pub fn new_variant_constructor(variant: Variant) -> Self {
+        Self::Variant(VariantConstructor::new(variant, Self::get_inc_func_id()))
+    }
+
+    pub fn id(&self) -> usize {
+        match self {
+            Callable::Native(native) => native.id,
+            Callable::Foreign(foreign) => foreign.id,
+            Callable::Class(class_initializer) => class_initializer.id,
+            Callable::Variant(constructor) => constructor.id,
+            Callable::Decorated(decorated) => decorated.id,
+        }
+    }
+
     fn get_inc_func_id() -> usize {
         // Assign new incrementing id to every new function.
         let mut id: usize = 0;
@@ -37,10 +61,19 @@ impl Callable {
 
     // pub fn call(&self)
 }
-// type FuncCall = dyn  Fn(Vec<Literals>) -> Result<Literals, RuntimeError<'_>>;
-// type FuncCall = dyn  Fn(Option<Vec<Literals>>) -> Literals;
-type FuncCall = dyn Fn(Vec<Literals>) -> Literals;
-// type FuncCall = fn(Vec<Literals>) -> Literals;
+/// Access to the running interpreter from native functions.
+pub trait NativeContext {
+    /// String representation of value, calling user defined `toString()` methods.
+    fn stringify(&mut self, value: &Literals) -> Result<String, RuntimeError>;
+    /// Runtime error reported at the call of native function.
+    fn error(&self, message: String) -> RuntimeError;
+    /// Runs source code in global scope, returning value of its last expression.
+    fn eval(&mut self, source: &str) -> Result<Literals, RuntimeError>;
+    /// Syntax tree of source code.
+    fn parse(&mut self, source: &str) -> Result<Vec<Stmt>, RuntimeError>;
+}
+
+type FuncCall = dyn Fn(&mut dyn NativeContext, Vec<Literals>) -> Result<Literals, RuntimeError>;
 
 #[derive(Clone)]
 pub struct NativeFn {
@@ -59,6 +92,21 @@ impl NativeFn {
             call: func
         }
     }
+
+    /// Native method of built-in type bound to value it's called on, which
+    /// is passed to the native as first argument.
+    pub fn bind(&self, this: Literals) -> Self {
+        let call = self.call.clone();
+        Self {
+            id: Callable::get_inc_func_id(),
+            name: self.name.clone(),
+            arity: self.arity,
+            call: Rc::new(move |ctx, mut args| {
+                args.insert(0, this.clone());
+                call(ctx, args)
+            })
+        }
+    }
 }
 
 impl Debug for NativeFn {
@@ -83,7 +131,15 @@ pub struct ForeignFn {
     pub name: String,
     pub arity: usize,
     declaration_stmt: Rc<Stmt>,
-    closure: Rc<RefCell<Scope>>
+    closure: Rc<RefCell<Scope>>,
+    /// Class initializer "init()" always returns the instance bound to 'this'.
+    is_initializer: bool,
+    /// Name of class whose body declares the function. Only such functions
+    /// access private members of the class through 'this'.
+    pub class: Option<Rc<Token>>,
+    /// Replacement returned by decorators of method, applied once when
+    /// the class is declared.
+    pub decorated: Option<DecoratedFn>
 }
 
 impl ForeignFn {
@@ -98,10 +154,54 @@ impl ForeignFn {
             name,
             arity,
             declaration_stmt,
-            closure
+            closure,
+            is_initializer: false,
+            class: None,
+            decorated: None
+        }
+    }
+
+    pub fn new_method(
+        declaration_stmt: Rc<Stmt>,
+        closure: Rc<RefCell<Scope>>,
+        is_initializer: bool,
+        class: Option<Rc<Token>>
+    ) -> Self {
+        let Stmt::Function { name, params, body: _, decorators: _, signature: _ } = declaration_stmt.as_ref() else {
+            panic!("Method must be a function declaration.")
+        };
+        Self {
+            id: Callable::get_inc_func_id(),
+            name: name.lexeme.to_string(),
+            arity: params.len(),
+            declaration_stmt: declaration_stmt.clone(),
+            closure,
+            is_initializer,
+            class,
+            decorated: None
         }
     }
 
+    /// Creates copy of method with 'this' defined in a new scope
+    /// enclosing the method body.
+    pub fn bind(&self, this: Literals) -> Self {
+        let mut scope = Scope::new(Some(self.closure.clone()));
+        scope.values.insert("this".to_string(), Some(this));
+        Self {
+            id: Callable::get_inc_func_id(),
+            closure: Rc::new(RefCell::new(scope)),
+            ..self.clone()
+        }
+    }
+
+    /// Method bound to a 'this' shared by all instances, as given to its
+    /// decorators. Returns the method and the scope defining its 'this'.
+    pub fn bind_shared(&self) -> (Self, Rc<RefCell<Scope>>) {
+        let method = self.bind(Literals::Nil);
+        let this_scope = method.closure.clone();
+        (method, this_scope)
+    }
+
     pub fn call<T: Print>(
         &self,
         intrprtr: &mut Interpreter<T>,
@@ -111,7 +211,7 @@ impl ForeignFn {
         // let declaration = &declaration_refs[self.declaration_stmt_index].clone();
         let declaration = self.declaration_stmt.as_ref();
         let Stmt::Function { 
-            name, params, body 
+            name, params, body, decorators: _, signature: _
         } = declaration else { 
             panic!("Non Callable object being called.")
         };
@@ -124,6 +224,7 @@ impl ForeignFn {
 
         let back_to_scope = intrprtr.environment.scope.clone();
         intrprtr.environment.scope = self.closure.clone();
+        let back_to_class = std::mem::replace(&mut intrprtr.current_class, self.class.clone());
         intrprtr.environment.create_new_scope();
 
         // Assign arguments to variables in current environment.
@@ -131,12 +232,22 @@ impl ForeignFn {
             intrprtr.environment.define(param.lexeme.to_string(), Some(value))
         }
         // Execute function body.
-        let result = intrprtr.execute_block(body, false)?;
+        let result = intrprtr.execute_block(body, false);
 
         intrprtr.environment.end_latest_scope();
         intrprtr.environment.scope = back_to_scope;
+        intrprtr.current_class = back_to_class;
 
-        Ok(result.unwrap_or(Literals::Nil))
+        let result = result?;
+        if self.is_initializer {
+            let this = self.closure.borrow().values.get("this").cloned().flatten();
+            return Ok(this.unwrap_or(Literals::Nil));
+        }
+        // Resolver rejects break outside of loops, so it never leaves function body.
+        match result {
+            Some(Flow::Return(value)) => Ok(value),
+            _ => Ok(Literals::Nil),
+        }
     }
 }
 
@@ -149,7 +260,6 @@ impl PartialEq for ForeignFn {
 #[derive(Debug, Clone)]
 pub struct ClassInitializer {
     id: usize,
-    arity: usize,
     pub class: Rc<Class>
 }
 
@@ -162,23 +272,133 @@ impl ClassInitializer {
     pub fn new(class: Rc<Class>, id: usize) -> Self {
         Self {
             id,
-            class,
-            arity: 0
+            class
         }
     }
 
+    /// Arity of the class is arity of its "init()" method, if any.
+    pub fn arity(&self) -> usize {
+        self.class.find_method("init").map_or(0, |init| init.arity)
+    }
+
     pub fn call<T: Print>(
         &self,
-        interpreter: &Interpreter<T>,
+        interpreter: &mut Interpreter<T>,
         args: Vec<Literals>
     ) -> Result<Literals, RuntimeError> {
-        if self.arity != args.len() {
+        if self.arity() != args.len() {
             return Err(RuntimeError::new(
                 self.class.name.clone(), 
-                format!("Expected {} arguments, received {}", self.arity, args.len())
+                format!("Expected {} arguments, received {}", self.arity(), args.len())
             ));
         }
-        let instance = Instance::new(self.class.clone());
-        Ok(Literals::Instance(instance))
+        let instance = Literals::Instance(Instance::new(self.class.clone()));
+        if let Some(init) = self.class.find_method("init") {
+            init.bind(instance.clone()).call(interpreter, args)?;
+        }
+        Ok(instance)
+    }
+
+    /// Looks up static field of the class, or else class method bound to the class.
+    pub fn get(&self, property: Rc<Token>, accessor: Option<&Rc<Token>>) -> Result<Literals, RuntimeError> {
+        self.class.check_access(&property, accessor)?;
+        if let Some(value) = self.class.static_fields.borrow().get(&property.lexeme) {
+            return Ok(value.clone());
+        }
+        self.class.find_class_method(&property.lexeme)
+        .map(|method| Literals::Function(Callable::Foreign(
+            method.bind(Literals::Function(Callable::Class(self.clone())))
+        )))
+        .ok_or_else(|| RuntimeError::new(
+            property.clone(),
+            format!("Undefined static property '{}' of class '{}'", property.lexeme, self.class.name.lexeme)
+        ))
+    }
+
+    pub fn set(&self, property: Rc<Token>, value: Literals, accessor: Option<&Rc<Token>>) -> Result<(), RuntimeError> {
+        self.class.check_access(&property, accessor)?;
+        self.class.static_fields.borrow_mut().insert(property.lexeme.to_string(), value);
+        Ok(())
     }
-}
\ No newline at end of file
+}
+/// Decorated method bound to an instance. The method given to decorators
+/// finds 'this' in a scope shared by all instances of the class, which is
+/// set to the instance while the decorated method runs.
+#[derive(Debug, Clone)]
+pub struct DecoratedFn {
+    id: usize,
+    pub function: Rc<Literals>,
+    this_scope: Rc<RefCell<Scope>>,
+    this: Rc<Literals>
+}
+
+impl DecoratedFn {
+    pub fn new(function: Literals, this_scope: Rc<RefCell<Scope>>) -> Self {
+        Self {
+            id: Callable::get_inc_func_id(),
+            function: Rc::new(function),
+            this_scope,
+            this: Rc::new(Literals::Nil)
+        }
+    }
+
+    pub fn bind(&self, this: Literals) -> Self {
+        Self {
+            id: Callable::get_inc_func_id(),
+            this: Rc::new(this),
+            ..self.clone()
+        }
+    }
+
+    pub fn call<T: Print>(
+        &self,
+        intrprtr: &mut Interpreter<T>,
+        args: Vec<Literals>,
+        paren: &Rc<Token>
+    ) -> Result<Literals, RuntimeError> {
+        // Restored afterwards, so calls on other instances nested in this
+        // one don't change its 'this'.
+        let back_to_this = self.this_scope.borrow_mut().values
+            .insert("this".to_string(), Some(self.this.as_ref().clone()));
+        let result = intrprtr.call_value(self.function.as_ref().clone(), args, paren);
+        self.this_scope.borrow_mut().values.insert("this".to_string(), back_to_this.flatten());
+        result
+    }
+}
+
+impl PartialEq for DecoratedFn {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+/// Constructor of enum variant values carrying a payload.
+#[derive(Debug, Clone)]
+pub struct VariantConstructor {
+    id: usize,
+    pub variant: Variant
+}
+
+impl PartialEq for VariantConstructor {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+impl VariantConstructor {
+    pub fn new(variant: Variant, id: usize) -> Self {
+        Self {
+            id,
+            variant
+        }
+    }
+
+    pub fn call(&self, args: Vec<Literals>) -> Result<Literals, RuntimeError> {
+        if self.variant.fields.len() != args.len() {
+            return Err(RuntimeError::new(
+                self.variant.name.clone(), 
+                format!("Expected {} arguments, received {}", self.variant.fields.len(), args.len())
+            ));
+        }
+        Ok(Literals::Variant(self.variant.construct(args)))
+    }
+}
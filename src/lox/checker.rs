@@ -0,0 +1,965 @@
+use std::{collections::{HashMap, HashSet}, fmt::Display, rc::Rc};
+
+use super::{
+    error_reporter::ErrorReporter,
+    expr::{Expr, ExprType, Literals},
+    pattern::{Pattern, PatternType},
+    printer::Print,
+    stmt::{ClassDeclaration, MatchArm, Signature, Stmt, TypeAnnotation},
+    token::Token,
+    token_type::TokenType,
+};
+
+/// Static type of a value. Unannotated variables, parameters and return
+/// values have Dynamic type, which checks against any other type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    Dynamic,
+    Nil,
+    Number,
+    String,
+    Bool,
+    /// List( element )
+    List(Box<Type>),
+    /// Map( key, value )
+    Map(Box<Type>, Box<Type>),
+    Tuple,
+    /// Function( params, return_type ), params None if not known.
+    Function(Option<Vec<Type>>, Box<Type>),
+    /// Class( name ): class object, called to create its instances.
+    Class(String),
+    /// Instance( class_name )
+    Instance(String),
+    /// Enum( name ): enum object, holding its variants.
+    Enum(String),
+    /// Variant( enum_name ): value of any variant of the enum.
+    Variant(String),
+    /// Optional( type ): value of the type or nil, written `Type?`.
+    Optional(Box<Type>),
+}
+
+impl Type {
+    /// Whether value of type `value` can be stored where this type is expected.
+    pub fn accepts(&self, value: &Type) -> bool {
+        match (self, value) {
+            (Type::Dynamic, _) | (_, Type::Dynamic) => true,
+            // Dynamic value which may be nil is left to null-safety checks.
+            (_, Type::Optional(value)) if **value == Type::Dynamic => true,
+            (Type::Optional(_), Type::Nil) => true,
+            (Type::Optional(expected), Type::Optional(value)) => expected.accepts(value),
+            (Type::Optional(expected), value) => expected.accepts(value),
+            (Type::List(expected), Type::List(value)) => expected.accepts(value),
+            (Type::Map(expected_key, expected_value), Type::Map(key, value)) => {
+                expected_key.accepts(key) && expected_value.accepts(value)
+            }
+            (Type::Function(None, _), Type::Class(_)) => true,
+            (Type::Function(Some(expected_params), expected_return), Type::Function(Some(params), value_return)) => {
+                expected_params.len() == params.len()
+                    && expected_params.iter().zip(params).all(|(expected, param)| param.accepts(expected))
+                    && expected_return.accepts(value_return)
+            }
+            (Type::Function(_, expected_return), Type::Function(_, value_return)) => {
+                expected_return.accepts(value_return)
+            }
+            (expected, value) => expected == value,
+        }
+    }
+
+    /// Type of value which is either of the two types.
+    fn join(self, other: Type) -> Type {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Type::Nil, Type::Optional(t)) | (Type::Optional(t), Type::Nil) => Type::Optional(t),
+            (Type::Nil, t) | (t, Type::Nil) => Type::Optional(Box::new(t)),
+            (Type::Optional(a), b) | (b, Type::Optional(a)) if *a == b => Type::Optional(a),
+            _ => Type::Dynamic,
+        }
+    }
+
+    /// Whether value of the type is or may be nil.
+    fn is_nilable(&self) -> bool {
+        matches!(self, Type::Nil | Type::Optional(_))
+    }
+
+    /// Type without nil.
+    fn without_nil(&self) -> Type {
+        match self {
+            Type::Optional(t) => (**t).clone(),
+            t => t.clone(),
+        }
+    }
+
+    /// Type of variable of declared type after value of given type is
+    /// stored in it, tracking only whether it may be nil.
+    fn narrow(&self, value: &Type) -> Type {
+        match value {
+            Type::Nil => Type::Nil,
+            Type::Optional(_) => Type::Optional(Box::new(self.without_nil())),
+            Type::Dynamic => self.clone(),
+            _ => self.without_nil(),
+        }
+    }
+
+    /// Whether instances may overload operators applied to value of the type.
+    fn is_overloadable(&self) -> bool {
+        matches!(self, Type::Dynamic | Type::Instance(_))
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::Dynamic => write!(f, "Any"),
+            Type::Nil => write!(f, "Nil"),
+            Type::Number => write!(f, "Number"),
+            Type::String => write!(f, "String"),
+            Type::Bool => write!(f, "Bool"),
+            Type::List(element) => write!(f, "List<{element}>"),
+            Type::Map(key, value) => write!(f, "Map<{key}, {value}>"),
+            Type::Tuple => write!(f, "Tuple"),
+            Type::Function(_, _) => write!(f, "Function"),
+            Type::Class(name) => write!(f, "class {name}"),
+            Type::Instance(name) | Type::Variant(name) => write!(f, "{name}"),
+            Type::Enum(name) => write!(f, "enum {name}"),
+            Type::Optional(t) => write!(f, "{t}?"),
+        }
+    }
+}
+
+/// Variable by its name and index of the scope declaring it.
+type VariableKey = (usize, String);
+
+/// Variables known to be nil (true) or not nil (false).
+type NilFacts = Vec<(Rc<Token>, bool)>;
+
+/// Gradual type checking pass, run after resolver and before interpreter.
+/// Checks values against annotated types of variables, parameters and
+/// return values, and operands of operators against the operators.
+///
+/// Along the way it tracks which variables may be nil at every point of a
+/// function, and warns when such values are used in arithmetic, called, or
+/// have their properties accessed. Conditions like `x != nil` narrow the
+/// variable in the branch they guard.
+pub struct TypeChecker<'a, 'p, T: Print> {
+    err_reporter: &'a ErrorReporter<'a, 'p, T>,
+    /// Types of variables declared in every scope, global scope first.
+    /// Variables missing from scopes, such as natives, are dynamic.
+    scopes: Vec<HashMap<String, Type>>,
+    /// Types named by class, enum and trait declarations.
+    types: HashMap<String, Type>,
+    /// Payload field count of every variant of declared enums, None for
+    /// variants without payload.
+    variants: HashMap<String, HashMap<String, Option<usize>>>,
+    /// Expected type of values returned from the current function.
+    return_type: Type,
+    /// Type of 'this' in the current method.
+    this_type: Type,
+    /// Types of variables at the current point of the function, narrower
+    /// than declared types by whether they may be nil.
+    flow: HashMap<VariableKey, Type>,
+    /// Variables assigned in nested functions, which may change at any
+    /// call, so they are never narrowed.
+    captured: HashSet<VariableKey>,
+    /// Index of the first scope of the current function.
+    function_scope: usize,
+    /// Number of break statements checked so far, to tell whether a loop
+    /// body may leave the loop other than through its condition.
+    breaks: usize,
+}
+
+impl<'a, 'p, T: Print> TypeChecker<'a, 'p, T> {
+    pub fn new(err_reporter: &'a ErrorReporter<'a, 'p, T>) -> Self {
+        Self {
+            err_reporter,
+            scopes: vec![HashMap::new()],
+            types: HashMap::new(),
+            variants: HashMap::new(),
+            return_type: Type::Dynamic,
+            this_type: Type::Dynamic,
+            flow: HashMap::new(),
+            captured: HashSet::new(),
+            function_scope: 0,
+            breaks: 0,
+        }
+    }
+
+    pub fn check(&mut self, stmts: &[Stmt]) {
+        // Types declared at top level can be named before their declaration.
+        for stmt in stmts {
+            self.declare_type(stmt);
+        }
+        for stmt in stmts {
+            self.check_stmt(stmt);
+        }
+    }
+
+    fn declare_type(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Class(class) => {
+                let name = class.name.lexeme.to_string();
+                self.types.insert(name.clone(), Type::Instance(name));
+            }
+            Stmt::Enum { name, variants } => {
+                let fields = variants.iter()
+                    .map(|variant| (variant.name.lexeme.to_string(), variant.fields.as_ref().map(Vec::len)))
+                    .collect();
+                self.variants.insert(name.lexeme.to_string(), fields);
+                self.types.insert(name.lexeme.to_string(), Type::Variant(name.lexeme.to_string()));
+            }
+            // Classes using a trait aren't tracked, so values of trait type are dynamic.
+            Stmt::Trait { name, .. } => {
+                self.types.insert(name.lexeme.to_string(), Type::Dynamic);
+            }
+            _ => (),
+        }
+    }
+
+    fn check_stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Expression(expr) | Stmt::Print(expr) => {
+                self.check_expr(expr);
+            }
+            Stmt::Function { name, params, body, decorators, signature } => {
+                for decorator in decorators {
+                    self.check_expr(&decorator.expression);
+                }
+                let (param_types, return_type) = self.signature_types(signature);
+                // Decorators may replace the function with any value.
+                let function_type = if decorators.is_empty() {
+                    Type::Function(Some(param_types.clone()), Box::new(return_type.clone()))
+                } else {
+                    Type::Dynamic
+                };
+                self.declare(name, function_type);
+                self.check_function(params, body, param_types, return_type);
+            }
+            Stmt::If(condition, then_stmt, else_stmt) => {
+                self.check_expr(condition);
+                let (when_true, when_false) = Self::nil_facts(condition);
+                let before = self.flow.clone();
+                self.apply_facts(&when_true);
+                self.check_stmt(then_stmt);
+                let after_then = std::mem::replace(&mut self.flow, before);
+                self.apply_facts(&when_false);
+                if let Some(else_stmt) = else_stmt.as_ref() {
+                    self.check_stmt(else_stmt);
+                }
+                // Branch leaving the function or loop doesn't reach code after the if.
+                if then_stmt.diverges() {
+                    return;
+                }
+                let after_else = std::mem::take(&mut self.flow);
+                self.flow = if else_stmt.as_ref().as_ref().is_some_and(Stmt::diverges) {
+                    after_then
+                } else {
+                    Self::join_flow(after_then, after_else)
+                };
+            }
+            Stmt::Var(pattern, annotation, initializer) => {
+                self.check_declaration(pattern, annotation.as_ref(), initializer.as_ref())
+            }
+            Stmt::Const(pattern, annotation, initializer) => {
+                self.check_declaration(pattern, annotation.as_ref(), Some(initializer))
+            }
+            Stmt::Block(stmts) => {
+                self.begin_scope();
+                self.check_stmts(stmts);
+                self.end_scope();
+            }
+            Stmt::While { label: _, condition, body, do_while: true } => {
+                // Body runs first, before the condition says anything.
+                let breaks = self.breaks;
+                self.check_stmt(body);
+                self.check_expr(condition);
+                self.apply_exit_facts(condition, breaks);
+            }
+            Stmt::While { label: _, condition, body, do_while: false } => {
+                // Body is checked once, as it runs first time.
+                self.check_expr(condition);
+                let before = self.flow.clone();
+                self.apply_facts(&Self::nil_facts(condition).0);
+                let breaks = self.breaks;
+                self.check_stmt(body);
+                let after = std::mem::take(&mut self.flow);
+                self.flow = Self::join_flow(before, after);
+                self.apply_exit_facts(condition, breaks);
+            }
+            Stmt::Break { .. } => self.breaks += 1,
+            Stmt::Match { keyword: _, subject, arms } => self.check_match_stmt(subject, arms),
+            Stmt::Return { return_keyword, expression } => {
+                let value = match expression {
+                    Some(expression) => self.check_value(expression, &self.return_type.clone()),
+                    None => Type::Nil,
+                };
+                if !self.return_type.accepts(&value) {
+                    self.err_reporter.error_token(
+                        return_keyword.clone(),
+                        &format!("Can't return value of type '{}' from function returning '{}'", value, self.return_type),
+                    );
+                }
+            }
+            Stmt::Class(class) => self.check_class_stmt(class),
+            Stmt::Trait { name, methods, required: _ } => {
+                self.declare_type(stmt);
+                self.declare(name, Type::Dynamic);
+                self.check_methods(methods, Type::Dynamic);
+            }
+            Stmt::Enum { name, variants: _ } => {
+                self.declare_type(stmt);
+                self.declare(name, Type::Enum(name.lexeme.to_string()));
+            }
+            Stmt::Extend { name, class: _, methods } => {
+                let this_type = match self.annotation_name_type(&name.lexeme) {
+                    Some(Type::Instance(class)) => Type::Instance(class),
+                    Some(builtin @ (Type::Number | Type::String | Type::Bool | Type::Tuple)) => builtin,
+                    Some(Type::List(_)) => Type::List(Box::new(Type::Dynamic)),
+                    Some(Type::Map(_, _)) => Type::Map(Box::new(Type::Dynamic), Box::new(Type::Dynamic)),
+                    _ => Type::Dynamic,
+                };
+                self.check_methods(methods, this_type);
+            }
+            Stmt::Macro(_) => unreachable!("Macros are expanded before type checking"),
+        }
+    }
+
+    fn check_stmts(&mut self, stmts: &[Stmt]) {
+        for stmt in stmts {
+            self.check_stmt(stmt);
+        }
+    }
+
+    fn check_declaration(&mut self, pattern: &Pattern, annotation: Option<&TypeAnnotation>, initializer: Option<&Expr>) {
+        let declared = annotation.map(|annotation| self.annotation_type(annotation));
+        let value = match initializer {
+            Some(initializer) => self.check_value(initializer, declared.as_ref().unwrap_or(&Type::Dynamic)),
+            None => Type::Nil,
+        };
+        if let Some(declared) = &declared {
+            if initializer.is_none() && !declared.accepts(&Type::Nil) {
+                self.err_reporter.error_token(
+                    pattern.token(),
+                    &format!("Variable '{}' of type '{}' must be initialized", pattern, declared),
+                );
+            } else if !declared.accepts(&value) {
+                self.err_reporter.error_token(
+                    pattern.token(),
+                    &format!("Can't initialize '{}' of type '{}' with value of type '{}'", pattern, declared, value),
+                );
+            }
+        }
+        match &pattern.pattern_type {
+            PatternType::Binding(name) => {
+                let declared = declared.unwrap_or(Type::Dynamic);
+                let narrowed = declared.narrow(&value);
+                self.declare(name, declared);
+                self.assign_flow(name, narrowed);
+            }
+            _ => self.declare_bindings(pattern),
+        }
+    }
+
+    fn check_function(&mut self, params: &[Rc<Token>], body: &[Stmt], param_types: Vec<Type>, return_type: Type) {
+        let enclosing_return_type = std::mem::replace(&mut self.return_type, return_type);
+        // Variables of enclosing functions may change before the function
+        // is called, so they start out as declared.
+        let enclosing_flow = std::mem::take(&mut self.flow);
+        let enclosing_function_scope = std::mem::replace(&mut self.function_scope, self.scopes.len());
+        self.begin_scope();
+        for (param, param_type) in params.iter().zip(param_types) {
+            self.declare(param, param_type);
+        }
+        self.check_stmts(body);
+        self.end_scope();
+        self.function_scope = enclosing_function_scope;
+        self.flow = enclosing_flow;
+        self.return_type = enclosing_return_type;
+    }
+
+    /// Types of parameters and return value of function, dynamic where
+    /// not annotated.
+    fn signature_types(&self, signature: &Signature) -> (Vec<Type>, Type) {
+        let annotated = |annotation: &Option<TypeAnnotation>| {
+            annotation.as_ref().map_or(Type::Dynamic, |annotation| self.annotation_type(annotation))
+        };
+        let params = signature.params.iter().map(annotated).collect();
+        (params, annotated(&signature.return_type))
+    }
+
+    fn check_class_stmt(&mut self, class: &ClassDeclaration) {
+        let name = class.name.lexeme.to_string();
+        self.types.insert(name.clone(), Type::Instance(name.clone()));
+        self.declare(&class.name, Type::Class(name.clone()));
+        for class_trait in &class.traits {
+            self.check_expr(class_trait);
+        }
+        for field in &class.static_fields {
+            if let Some(initializer) = &field.initializer {
+                self.check_expr(initializer);
+            }
+        }
+        self.check_methods(&class.methods, Type::Instance(name.clone()));
+        self.check_methods(&class.getters, Type::Instance(name.clone()));
+        self.check_methods(&class.setters, Type::Instance(name.clone()));
+        self.check_methods(&class.class_methods, Type::Class(name));
+    }
+
+    fn check_methods(&mut self, methods: &[Stmt], this_type: Type) {
+        let enclosing_this_type = std::mem::replace(&mut self.this_type, this_type);
+        for method in methods {
+            let Stmt::Function { name: _, params, body, decorators, signature } = method else {
+                unreachable!("Class body only contains method declarations")
+            };
+            for decorator in decorators {
+                self.check_expr(&decorator.expression);
+            }
+            let (param_types, return_type) = self.signature_types(signature);
+            self.check_function(params, body, param_types, return_type);
+        }
+        self.this_type = enclosing_this_type;
+    }
+
+    fn check_match_stmt(&mut self, subject: &Expr, arms: &[MatchArm]) {
+        self.check_expr(subject);
+        let before = self.flow.clone();
+        let mut after = before.clone();
+        for arm in arms {
+            self.flow = before.clone();
+            self.begin_scope();
+            self.declare_bindings(&arm.pattern);
+            if let Some(guard) = &arm.guard {
+                self.check_expr(guard);
+            }
+            self.check_stmt(&arm.body);
+            self.end_scope();
+            after = Self::join_flow(after, std::mem::take(&mut self.flow));
+        }
+        self.flow = after;
+    }
+
+    fn check_expr(&mut self, expr: &Expr) -> Type {
+        match &expr.expr_type {
+            ExprType::Assign(name, value) => {
+                let declared = self.lookup(name);
+                let value = self.check_value(value, &declared);
+                if !declared.accepts(&value) {
+                    self.err_reporter.error_token(
+                        name.clone(),
+                        &format!("Can't assign value of type '{}' to '{}' of type '{}'", value, name.lexeme, declared),
+                    );
+                }
+                self.assign_flow(name, declared.narrow(&value));
+                value
+            }
+            ExprType::AssignPattern(pattern, value) => {
+                let value = self.check_expr(value);
+                for (_, name) in pattern.bindings() {
+                    self.assign_flow(name, self.lookup(name));
+                }
+                value
+            }
+            ExprType::Binary(left_expr, op, right_expr) => {
+                let left = self.check_expr(left_expr);
+                let right = self.check_expr(right_expr);
+                if matches!(
+                    op.token_type,
+                    TokenType::Plus | TokenType::Minus | TokenType::Star | TokenType::Slash
+                    | TokenType::Greater | TokenType::GreaterEqual | TokenType::Less | TokenType::LessEqual
+                ) {
+                    let operand = format!("operand of '{}'", op.lexeme);
+                    self.warn_nil(op, left_expr, &left, &operand);
+                    self.warn_nil(op, right_expr, &right, &operand);
+                }
+                self.check_binary(op, left, right)
+            }
+            ExprType::Grouping(expr) => self.check_expr(expr),
+            ExprType::Literal(literal) => Self::literal_type(literal),
+            ExprType::Logical(left_expr, op, right) => {
+                let left = self.check_expr(left_expr);
+                // Right operand runs only if left one is truthy for 'and', falsey for 'or'.
+                let (when_true, when_false) = Self::nil_facts(left_expr);
+                let before = self.flow.clone();
+                self.apply_facts(if op.token_type == TokenType::And { &when_true } else { &when_false });
+                let right = self.check_expr(right);
+                let after = std::mem::take(&mut self.flow);
+                self.flow = Self::join_flow(before, after);
+                left.join(right)
+            }
+            ExprType::Unary(op, right_expr) => {
+                let right = self.check_expr(right_expr);
+                if op.token_type == TokenType::Minus {
+                    self.warn_nil(op, right_expr, &right, "operand of '-'");
+                }
+                match op.token_type {
+                    TokenType::Minus if right == Type::Number || right.is_overloadable() => right,
+                    TokenType::Minus if right.is_nilable() => Type::Dynamic,
+                    TokenType::Minus => {
+                        self.err_reporter.error_token(
+                            op.clone(),
+                            &format!("Operand of '-' must be a number, found '{}'", right),
+                        );
+                        Type::Dynamic
+                    }
+                    TokenType::Typeof => Type::String,
+                    _ => Type::Bool,
+                }
+            }
+            ExprType::Variable(name) => self.current_type(name),
+            ExprType::Call { callee: callee_expr, paren, arguments } => {
+                let callee = self.check_expr(callee_expr);
+                self.warn_nil(paren, callee_expr, &callee, "called value");
+                let params = match &callee {
+                    Type::Function(Some(params), _) => params.clone(),
+                    _ => vec![],
+                };
+                let arguments: Vec<Type> = arguments.iter().enumerate()
+                    .map(|(i, argument)| self.check_value(argument, params.get(i).unwrap_or(&Type::Dynamic)))
+                    .collect();
+                self.check_call(paren, callee, arguments)
+            }
+            ExprType::Get { object: object_expr, property } => {
+                let object = self.check_expr(object_expr);
+                self.warn_nil(property, object_expr, &object, &format!("object of property '{}'", property.lexeme));
+                self.property_type(&object, property)
+            }
+            ExprType::This(_) => self.this_type.clone(),
+            ExprType::Set { object: object_expr, property, value } => {
+                let object = self.check_expr(object_expr);
+                self.warn_nil(property, object_expr, &object, &format!("object of property '{}'", property.lexeme));
+                self.check_expr(value)
+            }
+            ExprType::List { bracket: _, elements } => {
+                let element = elements.iter()
+                    .map(|element| self.check_expr(element))
+                    .reduce(Type::join)
+                    .unwrap_or(Type::Dynamic);
+                Type::List(Box::new(element))
+            }
+            ExprType::Tuple { paren: _, elements } => {
+                for element in elements {
+                    self.check_expr(element);
+                }
+                Type::Tuple
+            }
+            ExprType::Map { brace: _, entries } => {
+                let (keys, values): (Vec<Type>, Vec<Type>) = entries.iter()
+                    .map(|(key, value)| (self.check_expr(key), self.check_expr(value)))
+                    .unzip();
+                let key = keys.into_iter().reduce(Type::join).unwrap_or(Type::Dynamic);
+                let value = values.into_iter().reduce(Type::join).unwrap_or(Type::Dynamic);
+                Type::Map(Box::new(key), Box::new(value))
+            }
+            ExprType::Index { object, bracket: _, index } => {
+                let object = self.check_expr(object);
+                self.check_expr(index);
+                match object {
+                    Type::List(element) => *element,
+                    Type::Map(_, value) => *value,
+                    _ => Type::Dynamic,
+                }
+            }
+            ExprType::SetIndex { object, bracket, index, value } => {
+                let value = self.check_expr(value);
+                let object = self.check_expr(object);
+                self.check_expr(index);
+                let element = match &object {
+                    Type::List(element) => element,
+                    Type::Map(_, value) => value,
+                    _ => return value,
+                };
+                if !element.accepts(&value) {
+                    self.err_reporter.error_token(
+                        bracket.clone(),
+                        &format!("Can't store value of type '{}' in '{}'", value, object),
+                    );
+                }
+                value
+            }
+            ExprType::Block { brace: _, statements, value } => {
+                self.begin_scope();
+                self.check_stmts(statements);
+                let value = value.as_ref().map_or(Type::Nil, |value| self.check_expr(value));
+                self.end_scope();
+                value
+            }
+            ExprType::If { keyword: _, condition, then_branch, else_branch } => {
+                self.check_expr(condition);
+                let (when_true, when_false) = Self::nil_facts(condition);
+                let before = self.flow.clone();
+                self.apply_facts(&when_true);
+                let then_type = self.check_expr(then_branch);
+                let after_then = std::mem::replace(&mut self.flow, before);
+                self.apply_facts(&when_false);
+                let else_type = else_branch.as_ref().map_or(Type::Nil, |else_branch| self.check_expr(else_branch));
+                let after_else = std::mem::take(&mut self.flow);
+                self.flow = Self::join_flow(after_then, after_else);
+                then_type.join(else_type)
+            }
+        }
+    }
+
+    /// Type of value stored where type `expected` is declared. Elements of
+    /// different types join to a dynamic type, so each element of a list or
+    /// map literal is then checked against the declared element type.
+    fn check_value(&mut self, expr: &Expr, expected: &Type) -> Type {
+        match (&expr.expr_type, expected.without_nil()) {
+            (ExprType::List { bracket, elements }, Type::List(element)) => {
+                let values = elements.iter()
+                    .map(|value| self.check_value(value, &element))
+                    .collect();
+                Type::List(Box::new(self.join_elements(bracket, "List element", values, *element)))
+            }
+            (ExprType::Map { brace, entries }, Type::Map(key, value)) => {
+                let (keys, values): (Vec<Type>, Vec<Type>) = entries.iter()
+                    .map(|(key_expr, value_expr)| (self.check_value(key_expr, &key), self.check_value(value_expr, &value)))
+                    .unzip();
+                Type::Map(
+                    Box::new(self.join_elements(brace, "Map key", keys, *key)),
+                    Box::new(self.join_elements(brace, "Map value", values, *value)),
+                )
+            }
+            _ => self.check_expr(expr),
+        }
+    }
+
+    /// Joined type of elements, or the declared type once elements of
+    /// different types were each checked against it.
+    fn join_elements(&self, token: &Rc<Token>, kind: &str, elements: Vec<Type>, declared: Type) -> Type {
+        let joined = elements.iter().cloned().reduce(Type::join).unwrap_or(Type::Dynamic);
+        if joined != Type::Dynamic || declared == Type::Dynamic {
+            return joined;
+        }
+        for element in elements.iter().filter(|element| !declared.accepts(element)) {
+            self.err_reporter.error_token(
+                token.clone(),
+                &format!("{} of type '{}' doesn't match declared type '{}'", kind, element, declared),
+            );
+        }
+        declared
+    }
+
+    fn check_binary(&self, op: &Rc<Token>, left: Type, right: Type) -> Type {
+        let overloaded = left.is_overloadable() || right.is_overloadable();
+        match op.token_type {
+            TokenType::Plus => match (&left, &right) {
+                (Type::Number, Type::Number) => Type::Number,
+                (Type::String, Type::String) => Type::String,
+                _ if overloaded => Type::Dynamic,
+                _ => self.operands_error(op, "two numbers or two strings", &left, &right),
+            },
+            TokenType::Minus | TokenType::Star | TokenType::Slash => match (&left, &right) {
+                (Type::Number, Type::Number) => Type::Number,
+                _ if overloaded => Type::Dynamic,
+                _ => self.operands_error(op, "numbers", &left, &right),
+            },
+            TokenType::Greater | TokenType::GreaterEqual | TokenType::Less | TokenType::LessEqual => {
+                match (&left, &right) {
+                    (Type::Number, Type::Number) => Type::Bool,
+                    _ if overloaded => Type::Dynamic,
+                    _ => self.operands_error(op, "numbers", &left, &right),
+                }
+            }
+            TokenType::EqualEqual | TokenType::BangEqual if overloaded => Type::Dynamic,
+            _ => Type::Bool,
+        }
+    }
+
+    fn operands_error(&self, op: &Rc<Token>, expected: &str, left: &Type, right: &Type) -> Type {
+        // Nil operands are warned about by null-safety checks.
+        if left.is_nilable() || right.is_nilable() {
+            return Type::Dynamic;
+        }
+        self.err_reporter.error_token(
+            op.clone(),
+            &format!("Operands of '{}' must be {}, found '{}' and '{}'", op.lexeme, expected, left, right),
+        );
+        Type::Dynamic
+    }
+
+    fn check_call(&self, paren: &Rc<Token>, callee: Type, arguments: Vec<Type>) -> Type {
+        match callee {
+            Type::Function(Some(params), return_type) => {
+                if params.len() != arguments.len() {
+                    self.err_reporter.error_token(
+                        paren.clone(),
+                        &format!("Expected {} arguments, received {}", params.len(), arguments.len()),
+                    );
+                }
+                for (i, (param, argument)) in params.iter().zip(&arguments).enumerate() {
+                    if !param.accepts(argument) {
+                        self.err_reporter.error_token(
+                            paren.clone(),
+                            &format!("Argument {} of type '{}' doesn't match parameter of type '{}'", i + 1, argument, param),
+                        );
+                    }
+                }
+                *return_type
+            }
+            Type::Function(None, return_type) => *return_type,
+            Type::Class(name) => Type::Instance(name),
+            Type::Number | Type::String | Type::Bool | Type::List(_) | Type::Map(_, _) | Type::Tuple => {
+                self.err_reporter.error_token(
+                    paren.clone(),
+                    &format!("Can only call functions and classes, found '{}'", callee),
+                );
+                Type::Dynamic
+            }
+            _ => Type::Dynamic,
+        }
+    }
+
+    /// Type of property of value. Only variants of enums are known, other
+    /// properties are dynamic.
+    fn property_type(&self, object: &Type, property: &Rc<Token>) -> Type {
+        let Type::Enum(name) = object else {
+            return Type::Dynamic;
+        };
+        match self.variants.get(name).and_then(|variants| variants.get(&property.lexeme)) {
+            Some(None) => Type::Variant(name.to_string()),
+            Some(Some(fields)) => Type::Function(
+                Some(vec![Type::Dynamic; *fields]),
+                Box::new(Type::Variant(name.to_string())),
+            ),
+            None => Type::Dynamic,
+        }
+    }
+
+    fn literal_type(literal: &Literals) -> Type {
+        match literal {
+            Literals::Number(_) => Type::Number,
+            Literals::String(_) => Type::String,
+            Literals::Bool(_) => Type::Bool,
+            Literals::Nil => Type::Nil,
+            _ => Type::Dynamic,
+        }
+    }
+
+    fn annotation_type(&self, annotation: &TypeAnnotation) -> Type {
+        let name = &annotation.name.lexeme;
+        let arguments: Vec<Type> = annotation.arguments.iter()
+            .map(|argument| self.annotation_type(argument))
+            .collect();
+        let annotated = match (name.as_str(), arguments.as_slice()) {
+            ("List", [element]) => Type::List(Box::new(element.clone())),
+            ("Map", [key, value]) => Type::Map(Box::new(key.clone()), Box::new(value.clone())),
+            ("List", _) | ("Map", _) if !arguments.is_empty() => {
+                let count = if name == "List" { 1 } else { 2 };
+                self.err_reporter.error_token(
+                    annotation.name.clone(),
+                    &format!("Type '{}' takes {} type arguments, received {}", name, count, arguments.len()),
+                );
+                Type::Dynamic
+            }
+            (_, [_, ..]) => {
+                self.err_reporter.error_token(
+                    annotation.name.clone(),
+                    &format!("Type '{}' doesn't take type arguments", name),
+                );
+                Type::Dynamic
+            }
+            _ => match self.annotation_name_type(name) {
+                Some(annotated) => annotated,
+                None => {
+                    self.err_reporter.error_token(
+                        annotation.name.clone(),
+                        &format!("Unknown type '{}'", name),
+                    );
+                    Type::Dynamic
+                }
+            },
+        };
+        if annotation.optional && annotated != Type::Dynamic && annotated != Type::Nil {
+            return Type::Optional(Box::new(annotated));
+        }
+        annotated
+    }
+
+    /// Type named in annotation without type arguments.
+    fn annotation_name_type(&self, name: &str) -> Option<Type> {
+        match name {
+            "Any" => Some(Type::Dynamic),
+            "Nil" => Some(Type::Nil),
+            "Number" => Some(Type::Number),
+            "String" => Some(Type::String),
+            "Bool" => Some(Type::Bool),
+            "Tuple" => Some(Type::Tuple),
+            "Function" => Some(Type::Function(None, Box::new(Type::Dynamic))),
+            "List" => Some(Type::List(Box::new(Type::Dynamic))),
+            "Map" => Some(Type::Map(Box::new(Type::Dynamic), Box::new(Type::Dynamic))),
+            _ => self.types.get(name).cloned(),
+        }
+    }
+
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn end_scope(&mut self) {
+        self.scopes.pop();
+        let depth = self.scopes.len();
+        self.flow.retain(|(scope, _), _| *scope != depth);
+        self.captured.retain(|(scope, _)| *scope != depth);
+    }
+
+    fn declare(&mut self, name: &Rc<Token>, declared: Type) {
+        let key = (self.scopes.len() - 1, name.lexeme.to_string());
+        self.flow.remove(&key);
+        self.captured.remove(&key);
+        self.scopes.last_mut().unwrap().insert(name.lexeme.to_string(), declared);
+    }
+
+    /// Declares names bound by destructuring pattern, all dynamic.
+    fn declare_bindings(&mut self, pattern: &Pattern) {
+        for (_, name) in pattern.bindings() {
+            self.declare(name, Type::Dynamic);
+        }
+    }
+
+    fn lookup(&self, name: &Rc<Token>) -> Type {
+        self.scopes.iter().rev()
+            .find_map(|scope| scope.get(&name.lexeme))
+            .cloned()
+            .unwrap_or(Type::Dynamic)
+    }
+
+    fn variable_key(&self, name: &Rc<Token>) -> Option<VariableKey> {
+        self.scopes.iter().rposition(|scope| scope.contains_key(&name.lexeme))
+            .map(|depth| (depth, name.lexeme.to_string()))
+    }
+
+    /// Type of variable at the current point of the function.
+    fn current_type(&self, name: &Rc<Token>) -> Type {
+        self.variable_key(name)
+            .filter(|key| !self.captured.contains(key))
+            .and_then(|key| self.flow.get(&key).cloned())
+            .unwrap_or_else(|| self.lookup(name))
+    }
+
+    /// Records type of variable after assignment. Variables of enclosing
+    /// functions can't be tracked, as the function may run at any time.
+    fn assign_flow(&mut self, name: &Rc<Token>, narrowed: Type) {
+        let Some(key) = self.variable_key(name) else {
+            return;
+        };
+        if key.0 < self.function_scope {
+            self.captured.insert(key);
+        } else {
+            self.flow.insert(key, narrowed);
+        }
+    }
+
+    /// Variables known to be nil or not nil where condition is truthy, and
+    /// where it is falsey.
+    fn nil_facts(condition: &Expr) -> (NilFacts, NilFacts) {
+        match &condition.expr_type {
+            ExprType::Grouping(inner) => Self::nil_facts(inner),
+            ExprType::Variable(name) => (vec![(name.clone(), false)], vec![]),
+            ExprType::Unary(op, inner) if op.token_type == TokenType::Bang => {
+                let (when_true, when_false) = Self::nil_facts(inner);
+                (when_false, when_true)
+            }
+            ExprType::Binary(left, op, right)
+                if matches!(op.token_type, TokenType::EqualEqual | TokenType::BangEqual) =>
+            {
+                let name = match (&left.expr_type, &right.expr_type) {
+                    (ExprType::Variable(name), ExprType::Literal(Literals::Nil))
+                    | (ExprType::Literal(Literals::Nil), ExprType::Variable(name)) => name.clone(),
+                    _ => return (vec![], vec![]),
+                };
+                let is_nil = vec![(name.clone(), true)];
+                let not_nil = vec![(name, false)];
+                if op.token_type == TokenType::EqualEqual {
+                    (is_nil, not_nil)
+                } else {
+                    (not_nil, is_nil)
+                }
+            }
+            ExprType::Logical(left, op, right) => {
+                let (mut left_true, mut left_false) = Self::nil_facts(left);
+                let (right_true, right_false) = Self::nil_facts(right);
+                if op.token_type == TokenType::And {
+                    left_true.extend(right_true);
+                    (left_true, vec![])
+                } else {
+                    left_false.extend(right_false);
+                    (vec![], left_false)
+                }
+            }
+            _ => (vec![], vec![]),
+        }
+    }
+
+    /// Loop without break in its body only ends once its condition is falsey.
+    /// breaks is the number of break statements checked before the body.
+    fn apply_exit_facts(&mut self, condition: &Expr, breaks: usize) {
+        if self.breaks == breaks {
+            self.apply_facts(&Self::nil_facts(condition).1);
+        }
+    }
+
+    fn apply_facts(&mut self, facts: &NilFacts) {
+        for (name, is_nil) in facts {
+            let narrowed = if *is_nil {
+                Type::Nil
+            } else {
+                self.lookup(name).without_nil()
+            };
+            self.assign_flow(name, narrowed);
+        }
+    }
+
+    /// Joins types of variables coming from two branches of the function.
+    /// Variables missing from either branch are as declared.
+    fn join_flow(a: HashMap<VariableKey, Type>, mut b: HashMap<VariableKey, Type>) -> HashMap<VariableKey, Type> {
+        a.into_iter()
+            .filter_map(|(key, a_type)| b.remove(&key).map(|b_type| (key, a_type.join(b_type))))
+            .collect()
+    }
+
+    /// Warns when value used as `usage` is or may be nil.
+    fn warn_nil(&self, token: &Rc<Token>, expr: &Expr, value: &Type, usage: &str) {
+        let verdict = match value {
+            Type::Nil => "is",
+            Type::Optional(_) => "may be",
+            _ => return,
+        };
+        let value = match &expr.expr_type {
+            ExprType::Variable(name) => format!("'{}'", name.lexeme),
+            _ => "Value".to_string(),
+        };
+        self.err_reporter.warning_token(token.clone(), &format!("{value} {verdict} nil as {usage}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Type;
+
+    #[test]
+    fn accepts() {
+        let numbers = Type::List(Box::new(Type::Number));
+        assert!(numbers.accepts(&Type::List(Box::new(Type::Dynamic))));
+        assert!(!numbers.accepts(&Type::List(Box::new(Type::String))));
+        assert!(Type::Optional(Box::new(Type::String)).accepts(&Type::Nil));
+        assert!(!Type::String.accepts(&Type::Optional(Box::new(Type::String))));
+        assert!(Type::Dynamic.accepts(&Type::Instance("Point".to_string())));
+        assert!(!Type::Instance("Point".to_string()).accepts(&Type::Instance("Circle".to_string())));
+    }
+
+    #[test]
+    fn narrow() {
+        let optional = Type::Optional(Box::new(Type::Number));
+        assert_eq!(optional.narrow(&Type::Number), Type::Number);
+        assert_eq!(optional.narrow(&Type::Nil), Type::Nil);
+        assert_eq!(Type::Dynamic.narrow(&Type::Dynamic), Type::Dynamic);
+        assert_eq!(Type::Dynamic.narrow(&optional), Type::Optional(Box::new(Type::Dynamic)));
+    }
+
+    #[test]
+    fn join() {
+        assert_eq!(Type::Number.join(Type::Number), Type::Number);
+        assert_eq!(Type::Number.join(Type::Nil), Type::Optional(Box::new(Type::Number)));
+        assert_eq!(Type::Number.join(Type::String), Type::Dynamic);
+        assert_eq!(Type::Dynamic.join(Type::Nil), Type::Optional(Box::new(Type::Dynamic)));
+        assert_eq!(Type::List(Box::new(Type::Bool)).to_string(), "List<Bool>");
+    }
+}
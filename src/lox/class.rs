@@ -1,19 +1,90 @@
-use std::rc::Rc;
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
-use super::{callable::{Callable, ForeignFn}, stmt::Stmt, token::Token};
+use super::{callable::ForeignFn, expr::Literals, interpreter::RuntimeError, token::Token, token_type::TokenType};
 
 #[derive(Debug)]
 pub struct Class {
     pub name: Rc<Token>,
-    // methods: Vec<ForeignFn>
+    /// Methods declared in class body, and those added later with `extend`.
+    methods: RefCell<HashMap<String, ForeignFn>>,
+    /// Methods declared without parameter list, run on property access.
+    getters: HashMap<String, ForeignFn>,
+    /// Methods declared with `set`, run on property assignment.
+    setters: HashMap<String, ForeignFn>,
+    /// Metaclass table: methods declared with `class` and fields declared
+    /// with `static var`, both accessed on the class object itself.
+    class_methods: HashMap<String, ForeignFn>,
+    pub static_fields: RefCell<HashMap<String, Literals>>
 }
 
 impl Class {
-    pub fn new(name: Rc<Token>) -> Self {
-    // pub fn new(name: String, methods: Vec<ForeignFn>) -> Self {
+    pub fn new(
+        name: Rc<Token>,
+        methods: HashMap<String, ForeignFn>,
+        getters: HashMap<String, ForeignFn>,
+        setters: HashMap<String, ForeignFn>,
+        class_methods: HashMap<String, ForeignFn>
+    ) -> Self {
         Self {
             name,
-            // methods
+            methods: RefCell::new(methods),
+            getters,
+            setters,
+            class_methods,
+            static_fields: RefCell::new(HashMap::new())
         }
     }
-}
\ No newline at end of file
+
+    /// Private members, named with leading '#', are accessible only through
+    /// 'this' inside functions declared in the class body. accessor is the
+    /// class declaring the function accessing the member through 'this'.
+    pub fn check_access(&self, property: &Rc<Token>, accessor: Option<&Rc<Token>>) -> Result<(), RuntimeError> {
+        if property.token_type != TokenType::PrivateIdentifier
+            || accessor.is_some_and(|class| Rc::ptr_eq(class, &self.name))
+        {
+            return Ok(());
+        }
+        Err(RuntimeError::new(
+            property.clone(),
+            format!(
+                "Can't access private member '{}' of class '{}' outside of its methods through 'this'",
+                property.lexeme, self.name.lexeme
+            )
+        ))
+    }
+
+    pub fn find_method(&self, name: &str) -> Option<ForeignFn> {
+        self.methods.borrow().get(name).cloned()
+    }
+
+    /// Adds methods of `extend` declaration, which can't replace existing ones.
+    /// Either all methods are added or, on error, none of them.
+    pub fn extend(&self, methods: Vec<(Rc<Token>, ForeignFn)>) -> Result<(), RuntimeError> {
+        for (name, _) in &methods {
+            if self.methods.borrow().contains_key(&name.lexeme)
+                || self.getters.contains_key(&name.lexeme)
+            {
+                return Err(RuntimeError::new(
+                    name.clone(),
+                    format!("Class '{}' already has method '{}'", self.name.lexeme, name.lexeme)
+                ));
+            }
+        }
+        for (name, method) in methods {
+            self.methods.borrow_mut().insert(name.lexeme.to_string(), method);
+        }
+        Ok(())
+    }
+
+    pub fn find_getter(&self, name: &str) -> Option<&ForeignFn> {
+        self.getters.get(name)
+    }
+
+    pub fn find_setter(&self, name: &str) -> Option<&ForeignFn> {
+        self.setters.get(name)
+    }
+
+    pub fn find_class_method(&self, name: &str) -> Option<&ForeignFn> {
+        self.class_methods.get(name)
+    }
+}
@@ -0,0 +1,136 @@
+use std::{cell::Cell, collections::HashMap, fmt::Display, rc::Rc};
+
+use super::{callable::Callable, expr::Literals, interpreter::RuntimeError, stmt::EnumVariant, token::Token};
+
+thread_local!{ 
+    pub static ENUM_ID: Cell<usize> = const { Cell::new(1) };
+}
+
+/// Namespace of variants created by an enum declaration.
+#[derive(Debug)]
+pub struct Enum {
+    id: usize,
+    pub name: Rc<Token>,
+    /// Variants without payload are singleton values, while variants with
+    /// payload are constructors of new values.
+    variants: HashMap<String, Literals>
+}
+
+impl Enum {
+    pub fn new(name: Rc<Token>, declarations: &[EnumVariant]) -> Self {
+        let id = Self::get_inc_id();
+        let mut variants = HashMap::new();
+        for declaration in declarations {
+            let variant = Variant::new(
+                id,
+                name.clone(),
+                declaration.name.clone(),
+                declaration.fields.clone().unwrap_or_default(),
+            );
+            let value = if declaration.fields.is_some() {
+                Literals::Function(Callable::new_variant_constructor(variant))
+            } else {
+                Literals::Variant(variant)
+            };
+            variants.insert(declaration.name.lexeme.to_string(), value);
+        }
+        Self { id, name, variants }
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    fn get_inc_id() -> usize {
+        // Assign new incrementing id to every new enum and variant.
+        let mut id: usize = 0;
+        ENUM_ID.with(|enum_id| {
+            id = enum_id.get();
+            enum_id.set(id + 1);
+        });
+        id
+    }
+
+    pub fn get(&self, property: Rc<Token>) -> Result<Literals, RuntimeError> {
+        self.variants.get(&property.lexeme)
+        .cloned()
+        .ok_or_else(|| RuntimeError::new(
+            property.clone(), 
+            format!("Undefined variant '{}' of enum '{}'", property.lexeme, self.name.lexeme)
+        ))
+    }
+}
+
+impl PartialEq for Enum {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+/// Value of an enum variant. Variants compare by identity, so singleton
+/// variants are equal only to themselves.
+#[derive(Debug, Clone)]
+pub struct Variant {
+    id: usize,
+    enum_id: usize,
+    pub enum_name: Rc<Token>,
+    pub name: Rc<Token>,
+    pub fields: Rc<Vec<Rc<Token>>>,
+    pub values: Rc<Vec<Literals>>
+}
+
+impl Variant {
+    fn new(enum_id: usize, enum_name: Rc<Token>, name: Rc<Token>, fields: Vec<Rc<Token>>) -> Self {
+        Self {
+            id: Enum::get_inc_id(),
+            enum_id,
+            enum_name,
+            name,
+            fields: Rc::new(fields),
+            values: Rc::new(vec![])
+        }
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Creates new value of this variant carrying given payload.
+    pub fn construct(&self, values: Vec<Literals>) -> Self {
+        Self {
+            id: Enum::get_inc_id(),
+            values: Rc::new(values),
+            ..self.clone()
+        }
+    }
+
+    pub fn is_of(&self, enumeration: &Enum) -> bool {
+        self.enum_id == enumeration.id
+    }
+
+    pub fn is_variant_of(&self, enumeration: &Enum, variant_name: &str) -> bool {
+        self.enum_id == enumeration.id && self.name.lexeme == variant_name
+    }
+
+    pub fn get(&self, property: Rc<Token>) -> Result<Literals, RuntimeError> {
+        self.fields.iter()
+        .position(|field| field.lexeme == property.lexeme)
+        .and_then(|i| self.values.get(i).cloned())
+        .ok_or_else(|| RuntimeError::new(
+            property.clone(), 
+            format!("Undefined property '{}' of variant '{}'", property.lexeme, self)
+        ))
+    }
+}
+
+impl PartialEq for Variant {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Display for Variant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Literals::Variant(self.clone()))
+    }
+}
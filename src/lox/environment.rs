@@ -6,12 +6,15 @@ use super::{expr::Literals, interpreter::RuntimeError, token::Token};
 #[derive(Debug)]
 pub struct Scope {
     pub values: HashMap<String, Option<Literals>>,
+    /// constants: HashMap<name, declaration>
+    pub constants: HashMap<String, Rc<Token>>,
     pub enclosing: Option<Rc<RefCell<Scope>>>,
 }
 impl Scope {
     pub fn new(enclosing: Option<Rc<RefCell<Scope>>>) -> Self {
         Self {
             values: HashMap::new(),
+            constants: HashMap::new(),
             enclosing,
         }
     }
@@ -38,6 +41,37 @@ impl Environment {
         self.scope.borrow_mut().values.insert(name, value);
     }
 
+    /// Defines variable, function or type named by its declaration, which
+    /// can't redeclare a constant of the same scope.
+    pub fn define_declared(&self, declaration: &Rc<Token>, value: Option<Literals>) -> Result<(), RuntimeError> {
+        self.check_not_constant(declaration)?;
+        self.define(declaration.lexeme.to_string(), value);
+        Ok(())
+    }
+
+    pub fn define_constant(&self, declaration: Rc<Token>, value: Literals) -> Result<(), RuntimeError> {
+        self.check_not_constant(&declaration)?;
+        let mut scope = self.scope.borrow_mut();
+        scope.values.insert(declaration.lexeme.to_string(), Some(value));
+        scope.constants.insert(declaration.lexeme.to_string(), declaration);
+        Ok(())
+    }
+
+    /// Resolver reports redeclared constants within one program, while
+    /// global constant of an earlier run in the REPL is only known here.
+    fn check_not_constant(&self, declaration: &Rc<Token>) -> Result<(), RuntimeError> {
+        match self.scope.borrow().constants.get(&declaration.lexeme) {
+            Some(constant) => Err(RuntimeError::new(
+                declaration.clone(),
+                format!("Can't redeclare constant '{}'", declaration.lexeme),
+            ).with_note(
+                constant.clone(),
+                format!("'{}' is declared constant here", declaration.lexeme),
+            )),
+            None => Ok(()),
+        }
+    }
+
     pub fn get_at(&self, distance: usize, name: Rc<Token>) -> Result<Literals, RuntimeError> {
         let resolved_scope = self.get_ancestor(distance);
         let result = resolved_scope
@@ -100,6 +134,14 @@ impl Environment {
         name: Rc<Token>,
         value: Literals,
     ) -> Result<Literals, RuntimeError> {
+        // Assignments to local constants are rejected by resolver already.
+        if let Some(declaration) = self.globals.borrow().constants.get(&name.lexeme) {
+            let err_mssg = format!("Can't assign to constant '{}'", name.lexeme);
+            return Err(RuntimeError::new(name, err_mssg).with_note(
+                declaration.clone(),
+                format!("'{}' is declared constant here", declaration.lexeme),
+            ));
+        }
         if self.globals.borrow().values.contains_key(&name.lexeme) {
             self.globals
                 .borrow_mut()
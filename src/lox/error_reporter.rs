@@ -1,4 +1,4 @@
-use std::{cell::Cell, rc::Rc};
+use std::{cell::{Cell, RefCell}, rc::Rc};
 
 use super::{token::Token, printer::Print};
 
@@ -7,7 +7,10 @@ pub struct ErrorReporter<'a, 'p, T: Print> {
     pub had_error: Cell<bool>,
     pub had_runtime_error: Cell<bool>,
     pub source_code: &'a str,
-    pub printer: &'p T
+    pub printer: &'p T,
+    /// Errors as ( line, message ), collected instead of printed when
+    /// capturing. Warnings and notes are dropped then.
+    captured: Option<RefCell<Vec<(usize, String)>>>
 }
 
 impl<'a, 'p, T: Print> ErrorReporter<'a, 'p, T> {
@@ -17,26 +20,40 @@ impl<'a, 'p, T: Print> ErrorReporter<'a, 'p, T> {
             had_runtime_error: Cell::new(false),
             repl_mode,
             source_code,
-            printer
+            printer,
+            captured: None
         }
     }
+
+    /// Reporter collecting errors for the caller to handle, such as
+    /// natives running source code given to them.
+    pub fn capturing(mut self) -> Self {
+        self.captured = Some(RefCell::new(vec![]));
+        self
+    }
+
+    /// First error collected by capturing reporter.
+    pub fn first_error(&self) -> Option<(usize, String)> {
+        self.captured.as_ref()?.borrow().first().cloned()
+    }
     
     pub fn error(
         &self,
         line: usize, offset: usize,
         lexeme_length: usize, message: &str
     ) {
-        self.printer.print(&self.format(
-            line, offset, lexeme_length, message
-        ));
+        if let Some(captured) = &self.captured {
+            captured.borrow_mut().push((line, message.to_string()));
+        } else {
+            self.printer.print(&self.format(
+                line, offset, lexeme_length, message
+            ));
+        }
         self.had_error.set(true);
     }
 
     pub fn error_token(&self, token: Rc<Token>, message: &str) {
-        self.printer.print(&self.format(
-            token.line, 0, 0, message
-        ));
-        self.had_error.set(true);
+        self.error(token.line, 0, 0, message);
     }
 
     pub fn runtime_error(&self, token: Rc<Token>, message: String) {
@@ -46,11 +63,34 @@ impl<'a, 'p, T: Print> ErrorReporter<'a, 'p, T> {
         self.had_runtime_error.set(true);
     }
 
+    /// Reports likely mistake which doesn't stop the program from running.
+    pub fn warning_token(&self, token: Rc<Token>, message: &str) {
+        if self.captured.is_none() {
+            self.printer.print(&self.format_labeled(
+                "Warning", token.line, message
+            ));
+        }
+    }
+
+    /// Prints additional information about previously reported error,
+    /// such as location of a related declaration.
+    pub fn note_token(&self, token: Rc<Token>, message: &str) {
+        if self.captured.is_none() {
+            self.printer.print(&self.format_labeled(
+                "Note", token.line, message
+            ));
+        }
+    }
+
     fn format(&self,
         line: usize, _offset: usize,
         _lexeme_length: usize, message: &str
     ) -> String {
-        let mut error_mssg = String::from("Error: ");
+        self.format_labeled("Error", line, message)
+    }
+
+    fn format_labeled(&self, label: &str, line: usize, message: &str) -> String {
+        let mut error_mssg = format!("{label}: ");
         error_mssg.push_str(message);
         error_mssg.push('.');
         if !self.repl_mode {
@@ -64,7 +104,9 @@ impl<'a, 'p, T: Print> ErrorReporter<'a, 'p, T> {
 
 #[cfg(test)] 
 mod tests {
-    use crate::lox::printer::TestPrinter;
+    use std::rc::Rc;
+
+    use crate::lox::{printer::TestPrinter, token::Token, token_type::TokenType};
 
     use super::ErrorReporter;
 
@@ -85,4 +127,26 @@ mod tests {
         let msg = er.format(2, 3, 4, "Madeup Error");
         assert_eq!(msg, "Error: Madeup Error.\n   line 2 | world!");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn warning_is_not_error() {
+        let source_code = "hello\nworld!";
+        let printer = TestPrinter::default();
+        let er = ErrorReporter::new(source_code, false, &printer);
+        er.warning_token(Rc::new(Token::new(TokenType::Identifier, "world".to_string(), 2)), "Madeup Warning");
+        assert!(!er.had_error.get());
+        assert_eq!(printer.result.borrow().as_slice(), ["Warning: Madeup Warning.\n   line 2 | world!"]);
+    }
+
+    #[test]
+    fn capturing_errors() {
+        let source_code = "hello\nworld!";
+        let printer = TestPrinter::default();
+        let er = ErrorReporter::new(source_code, false, &printer).capturing();
+        er.error_token(Rc::new(Token::new(TokenType::Identifier, "world".to_string(), 2)), "Madeup Error");
+        er.error(1, 0, 0, "Another Error");
+        assert!(er.had_error.get());
+        assert_eq!(er.first_error(), Some((2, "Madeup Error".to_string())));
+        assert!(printer.result.borrow().is_empty());
+    }
+}
@@ -1,10 +1,15 @@
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+use std::mem::Discriminant;
 use std::rc::Rc;
 use std::cell::Cell;
 
-use super::{token::Token, callable::Callable, class::Class, instance::Instance};
+use super::{token::Token, callable::Callable, instance::Instance, list::List, map::Map, pattern::Pattern, enumeration::{Enum, Variant}, stmt::Stmt, traits::Trait};
 
 thread_local!{ 
-    pub static EXPR_ID: Cell<usize> = Cell::new(1);
+    pub static EXPR_ID: Cell<usize> = const { Cell::new(1) };
 }
 
 #[derive(Debug, Clone)]
@@ -17,7 +22,7 @@ impl Expr {
         Self { id: Self::get_inc_expr_id(), expr_type }
     }
 
-    fn get_inc_expr_id() -> usize {
+    pub fn get_inc_expr_id() -> usize {
         // Assign new incrementing id to every new expr.
         let mut id: usize = 0;
         EXPR_ID.with(|expr_id| {
@@ -31,6 +36,8 @@ impl Expr {
 pub enum ExprType {
     /// Assign(var_name, rvalue)
     Assign(Rc<Token>, Box<Expr>),
+    /// AssignPattern(target, rvalue)
+    AssignPattern(Pattern, Box<Expr>),
     /// Binary(left, operation, right)
     Binary(Box<Expr>, Rc<Token>, Box<Expr>),
     /// Grouping(expr)
@@ -45,10 +52,28 @@ pub enum ExprType {
     Variable(Rc<Token>),
     /// Call( callee, paren, arguments )
     Call{ callee: Box<Expr>, paren: Rc<Token>, arguments: Vec<Expr> },
-    ///
+    /// Get( object, property )
     Get { object: Box<Expr>, property: Rc<Token> },
-    ///
-    Set { object: Box<Expr>, property: Rc<Token>, value: Box<Expr> }
+    /// This( keyword )
+    This(Rc<Token>),
+    /// Set( object, property, value )
+    Set { object: Box<Expr>, property: Rc<Token>, value: Box<Expr> },
+    /// List( bracket, elements )
+    List { bracket: Rc<Token>, elements: Vec<Expr> },
+    /// Tuple( paren, elements )
+    Tuple { paren: Rc<Token>, elements: Vec<Expr> },
+    /// Map( brace, entries )
+    Map { brace: Rc<Token>, entries: Vec<(Expr, Expr)> },
+    /// Index( object, bracket, index )
+    Index { object: Box<Expr>, bracket: Rc<Token>, index: Box<Expr> },
+    /// SetIndex( object, bracket, index, value )
+    SetIndex { object: Box<Expr>, bracket: Rc<Token>, index: Box<Expr>, value: Box<Expr> },
+    /// Block( brace, statements, value ): value is the trailing expression
+    /// without ';', nil if there is none.
+    Block { brace: Rc<Token>, statements: Vec<Stmt>, value: Option<Box<Expr>> },
+    /// If( keyword, condition, then_branch, else_branch ): branches are
+    /// block expressions, else branch may also be another if expression.
+    If { keyword: Rc<Token>, condition: Box<Expr>, then_branch: Box<Expr>, else_branch: Option<Box<Expr>> }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -58,7 +83,264 @@ pub enum Literals {
     Bool(bool),
     Nil,
     Function(Callable),
-    Instance(Instance)
+    Instance(Instance),
+    List(List),
+    Map(Map),
+    Tuple(Rc<Vec<Literals>>),
+    Enum(Rc<Enum>),
+    Variant(Variant),
+    Trait(Rc<Trait>)
+}
+
+impl Literals {
+    /// String representation of value nested inside a collection or shown in
+    /// error messages. Strings are quoted to tell them apart from other values.
+    pub fn repr(&self) -> String {
+        let mut out = String::new();
+        let _ = self.write::<Infallible>(&mut out, true, &mut vec![], &mut |_| Ok(None));
+        out
+    }
+
+    /// String representation of value as printed by `print`.
+    /// `instance_to_string` may replace representation of any instance reached,
+    /// e.g. with result of its `toString()` method.
+    pub fn stringify<E>(
+        &self,
+        instance_to_string: &mut dyn FnMut(&Instance) -> Result<Option<String>, E>
+    ) -> Result<String, E> {
+        let mut out = String::new();
+        self.write(&mut out, false, &mut vec![], instance_to_string)?;
+        Ok(out)
+    }
+
+    /// Writes string representation of value into out. Instances, lists and
+    /// maps already being written further up the path are shown as `...`,
+    /// so recursive structures terminate.
+    fn write<E>(
+        &self,
+        out: &mut String,
+        nested: bool,
+        path: &mut Vec<(Discriminant<Literals>, usize)>,
+        instance_to_string: &mut dyn FnMut(&Instance) -> Result<Option<String>, E>
+    ) -> Result<(), E> {
+        let id = match self {
+            Literals::Instance(instance) => Some(instance.id()),
+            Literals::List(list) => Some(list.id()),
+            Literals::Map(map) => Some(map.id()),
+            _ => None
+        };
+        let key = id.map(|id| (std::mem::discriminant(self), id));
+        let is_cycle = key.is_some_and(|key| path.contains(&key));
+        if let Some(key) = key {
+            path.push(key);
+        }
+        // Popped before returning, also when writing a nested value failed
+        let result = self.write_value(out, nested, is_cycle, path, instance_to_string);
+        if key.is_some() {
+            path.pop();
+        }
+        result
+    }
+
+    fn write_value<E>(
+        &self,
+        out: &mut String,
+        nested: bool,
+        is_cycle: bool,
+        path: &mut Vec<(Discriminant<Literals>, usize)>,
+        instance_to_string: &mut dyn FnMut(&Instance) -> Result<Option<String>, E>
+    ) -> Result<(), E> {
+        match self {
+            Literals::String(s) if nested => out.push_str(&format!("{s:?}")),
+            Literals::String(s) => out.push_str(s),
+            Literals::Number(n) => out.push_str(&n.to_string()),
+            Literals::Bool(b) => out.push_str(&b.to_string()),
+            Literals::Nil => out.push_str("Nil"),
+            Literals::Function(Callable::Native(native)) => {
+                out.push_str(&format!("<native-fn {}()>", native.name))
+            }
+            Literals::Function(Callable::Foreign(foreign)) => {
+                out.push_str(&format!("<fn {}()>", foreign.name))
+            }
+            Literals::Function(Callable::Class(class_initializer)) => {
+                out.push_str(&format!("<class {}>", class_initializer.class.name.lexeme))
+            }
+            Literals::Function(Callable::Decorated(decorated)) => {
+                decorated.function.write(out, nested, path, instance_to_string)?
+            }
+            Literals::Function(Callable::Variant(constructor)) => {
+                let variant = &constructor.variant;
+                out.push_str(&format!("<fn {}.{}()>", variant.enum_name.lexeme, variant.name.lexeme))
+            }
+            Literals::Instance(instance) => {
+                let name = &instance.class.name.lexeme;
+                if is_cycle {
+                    out.push_str(&format!("<instance {name}{{...}}>"));
+                } else if let Some(s) = instance_to_string(instance)? {
+                    out.push_str(&s);
+                } else {
+                    // Fields are sorted to print the same way every time.
+                    let mut fields: Vec<(String, Literals)> = instance.fields.borrow()
+                        .iter()
+                        .map(|(k, v)| (k.clone(), v.clone()))
+                        .collect();
+                    fields.sort_by(|a, b| a.0.cmp(&b.0));
+                    out.push_str(&format!("<instance {name}{{"));
+                    for (i, (field, value)) in fields.iter().enumerate() {
+                        if i > 0 {
+                            out.push_str(", ");
+                        }
+                        out.push_str(&format!("{field}: "));
+                        value.write(out, true, path, instance_to_string)?;
+                    }
+                    out.push_str("}>");
+                }
+            }
+            Literals::List(_) if is_cycle => out.push_str("[...]"),
+            Literals::List(list) => {
+                let elements = list.elements.borrow().clone();
+                out.push('[');
+                Self::write_elements(&elements, out, path, instance_to_string)?;
+                out.push(']');
+            }
+            Literals::Map(_) if is_cycle => out.push_str("{...}"),
+            Literals::Map(map) => {
+                let entries = map.entries.borrow().clone();
+                out.push('{');
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    key.write(out, true, path, instance_to_string)?;
+                    out.push_str(": ");
+                    value.write(out, true, path, instance_to_string)?;
+                }
+                out.push('}');
+            }
+            Literals::Tuple(elements) => {
+                out.push('(');
+                Self::write_elements(elements, out, path, instance_to_string)?;
+                // Single element tuple is written as (a,)
+                if elements.len() == 1 {
+                    out.push(',');
+                }
+                out.push(')');
+            }
+            Literals::Enum(enumeration) => out.push_str(&format!("<enum {}>", enumeration.name.lexeme)),
+            Literals::Trait(t) => out.push_str(&format!("<trait {}>", t.name.lexeme)),
+            Literals::Variant(variant) => {
+                out.push_str(&format!("{}.{}", variant.enum_name.lexeme, variant.name.lexeme));
+                if !variant.values.is_empty() {
+                    out.push('(');
+                    Self::write_elements(&variant.values, out, path, instance_to_string)?;
+                    out.push(')');
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn write_elements<E>(
+        elements: &[Literals],
+        out: &mut String,
+        path: &mut Vec<(Discriminant<Literals>, usize)>,
+        instance_to_string: &mut dyn FnMut(&Instance) -> Result<Option<String>, E>
+    ) -> Result<(), E> {
+        for (i, element) in elements.iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            element.write(out, true, path, instance_to_string)?;
+        }
+        Ok(())
+    }
+
+    /// Marks instance, list or map as frozen so it can't be modified anymore.
+    /// Deep freeze also freezes every value reachable from it, including
+    /// values inside tuples and variant payloads. Other values are immutable
+    /// already and are left as they are.
+    pub fn freeze(&self, deep: bool) {
+        let mut visited = HashSet::new();
+        self.freeze_reachable(deep, &mut visited);
+    }
+
+    fn freeze_reachable(&self, deep: bool, visited: &mut HashSet<(Discriminant<Literals>, usize)>) {
+        // Remember mutable values already walked, so cyclic structures are walked once.
+        let id = match self {
+            Literals::Instance(instance) => Some(instance.id()),
+            Literals::List(list) => Some(list.id()),
+            Literals::Map(map) => Some(map.id()),
+            _ => None
+        };
+        if id.is_some_and(|id| !visited.insert((std::mem::discriminant(self), id))) {
+            return;
+        }
+        let children: Vec<Literals> = match self {
+            Literals::Instance(instance) => {
+                instance.freeze();
+                instance.fields.borrow().values().cloned().collect()
+            }
+            Literals::List(list) => {
+                list.freeze();
+                list.elements.borrow().clone()
+            }
+            Literals::Map(map) => {
+                map.freeze();
+                map.entries.borrow().iter()
+                    .flat_map(|(k, v)| [k.clone(), v.clone()])
+                    .collect()
+            }
+            Literals::Tuple(elements) => elements.to_vec(),
+            Literals::Variant(variant) => variant.values.to_vec(),
+            _ => vec![]
+        };
+        if deep {
+            for child in children {
+                child.freeze_reachable(deep, visited);
+            }
+        }
+    }
+
+    /// Instances, lists and maps are frozen once marked by `freeze`,
+    /// every other value can't be modified in the first place.
+    pub fn is_frozen(&self) -> bool {
+        match self {
+            Literals::Instance(instance) => instance.is_frozen(),
+            Literals::List(list) => list.is_frozen(),
+            Literals::Map(map) => map.is_frozen(),
+            _ => true
+        }
+    }
+}
+
+/// Hash consistent with equality: tuples hash by value, while functions,
+/// instances, lists, maps, enums, variants and traits hash by their identity.
+impl Hash for Literals {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Literals::String(s) => s.hash(state),
+            // 0.0 and -0.0 are equal, so they must hash the same.
+            Literals::Number(n) => (if *n == 0.0 { 0.0 } else { *n }).to_bits().hash(state),
+            Literals::Bool(b) => b.hash(state),
+            Literals::Nil => (),
+            Literals::Function(f) => f.id().hash(state),
+            Literals::Instance(instance) => instance.id().hash(state),
+            Literals::List(list) => list.id().hash(state),
+            Literals::Map(map) => map.id().hash(state),
+            Literals::Tuple(elements) => elements.hash(state),
+            Literals::Enum(enumeration) => enumeration.id().hash(state),
+            Literals::Variant(variant) => variant.id().hash(state),
+            Literals::Trait(t) => t.id().hash(state),
+        }
+    }
+}
+
+impl Display for Literals {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Ok(s) = self.stringify::<Infallible>(&mut |_| Ok(None));
+        write!(f, "{s}")
+    }
 }
 
 impl From<Literals> for Expr {
@@ -72,4 +354,35 @@ impl From<ExprType> for Expr {
     fn from(value: ExprType) -> Self {
         Self::new(value)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use std::{collections::{hash_map::DefaultHasher, HashMap}, hash::{Hash, Hasher}, rc::Rc};
+
+    use super::{Literals, super::{class::Class, instance::Instance, list::List, token::Token, token_type::TokenType}};
+
+    fn hash_of(literal: &Literals) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        literal.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn equal_tuples_hash_equal() {
+        let a = Literals::Tuple(Rc::new(vec![Literals::Number(0.0), Literals::String("x".to_string())]));
+        let b = Literals::Tuple(Rc::new(vec![Literals::Number(-0.0), Literals::String("x".to_string())]));
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+    }
+
+    #[test]
+    fn failed_write_leaves_path_empty() {
+        let name = Rc::new(Token::new(TokenType::Identifier, "A".to_string(), 1));
+        let class = Rc::new(Class::new(name, HashMap::new(), HashMap::new(), HashMap::new(), HashMap::new()));
+        let list = Literals::List(List::new(vec![Literals::Instance(Instance::new(class))]));
+        let mut path = vec![];
+        let result = list.write(&mut String::new(), false, &mut path, &mut |_| Err(()));
+        assert!(result.is_err());
+        assert!(path.is_empty());
+    }
+}
@@ -1,23 +1,24 @@
 use std::collections::HashMap;
 use std::rc::Rc;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 
+use super::callable::Callable;
 use super::class::Class;
 use super::expr::Literals;
-use super::interpreter::RuntimeError;
+use super::interpreter::{Interpreter, RuntimeError};
+use super::printer::Print;
 use super::token::Token;
 
 thread_local!{ 
-    pub static Instance_ID: Cell<usize> = Cell::new(1);
+    pub static INSTANCE_ID: Cell<usize> = const { Cell::new(1) };
 }
 
 #[derive(Debug, Clone)]
 pub struct Instance {
     id: usize,
-    // pub name: String,
-    // methods: Vec<ForeignFn>
     pub class: Rc<Class>,
-    fields: HashMap<String, Literals>
+    pub fields: Rc<RefCell<HashMap<String, Literals>>>,
+    frozen: Rc<Cell<bool>>
 }
 
 impl Instance {
@@ -25,34 +26,81 @@ impl Instance {
         Self {
             id: Self::get_inc_expr_id(),
             class,
-            fields: HashMap::new()
+            fields: Rc::new(RefCell::new(HashMap::new())),
+            frozen: Rc::new(Cell::new(false))
         }
     }
 
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
     fn get_inc_expr_id() -> usize {
         // Assign new incrementing id to every new instance.
         let mut id: usize = 0;
-        Instance_ID.with(|expr_id| {
+        INSTANCE_ID.with(|expr_id| {
             id = expr_id.get();
             expr_id.set(id + 1);
         });
         id
     }
 
-    pub fn get(&self, property: Rc<Token>) -> Result<Literals, RuntimeError> {
-        dbg!(&self.fields);
+    /// Looks up field of the instance, then getter of its class, which is run
+    /// right away, or else method of its class bound to the instance.
+    ///
+    /// accessor is the class declaring the function which accesses the
+    /// property through 'this', if any, to check access to private members.
+    pub fn get<T: Print>(
+        &self,
+        property: Rc<Token>,
+        interpreter: &mut Interpreter<T>,
+        accessor: Option<&Rc<Token>>
+    ) -> Result<Literals, RuntimeError> {
+        self.class.check_access(&property, accessor)?;
+        if let Some(value) = self.fields.borrow().get(&property.lexeme) {
+            return Ok(value.clone());
+        }
+        if let Some(getter) = self.class.find_getter(&property.lexeme) {
+            return getter.bind(Literals::Instance(self.clone())).call(interpreter, vec![]);
+        }
+        let Some(method) = self.class.find_method(&property.lexeme) else {
+            return Err(RuntimeError::new(
+                property.clone(), 
+                format!("Undefined property '{}'", property.lexeme)
+            ));
+        };
+        let this = Literals::Instance(self.clone());
+        if let Some(decorated) = &method.decorated {
+            return Ok(Literals::Function(Callable::Decorated(decorated.bind(this))));
+        }
+        Ok(Literals::Function(Callable::Foreign(method.bind(this))))
+    }
+
+    /// Whether instance has a field, or its class a getter or method, with this name.
+    pub fn has(&self, property: &str) -> bool {
+        self.fields.borrow().contains_key(property)
+            || self.class.find_getter(property).is_some()
+            || self.class.find_method(property).is_some()
+    }
+
+    pub fn set(&self, property: Rc<Token>, value: Literals, accessor: Option<&Rc<Token>>) -> Result<(), RuntimeError> {
+        self.class.check_access(&property, accessor)?;
+        if self.is_frozen() {
+            return Err(RuntimeError::new(
+                property.clone(),
+                format!("Can't set property '{}' on frozen instance of '{}'", property.lexeme, self.class.name.lexeme)
+            ));
+        }
+        self.fields.borrow_mut().insert(property.lexeme.to_string(), value);
+        Ok(())
+    }
 
-        self.fields.get(&property.lexeme)
-        .cloned()
-        .ok_or_else(|| RuntimeError::new(
-            property.clone(), 
-            format!("Undefined property '{}'", property.lexeme)
-        ))
+    pub fn freeze(&self) {
+        self.frozen.set(true);
     }
 
-    pub fn set(&mut self, property: Rc<Token>, value: Literals) {
-        self.fields.insert(property.lexeme.to_string(), value);
-        dbg!(&self.fields);
+    pub fn is_frozen(&self) -> bool {
+        self.frozen.get()
     }
 }
 
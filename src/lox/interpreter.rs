@@ -1,24 +1,97 @@
 use std::{collections::HashMap, rc::Rc, time::SystemTime};
 
 use super::{
-    callable::Callable,
+    builtins::{self, MethodTables},
+    callable::{Callable, DecoratedFn, ForeignFn, NativeContext},
     environment::Environment,
+    enumeration::Enum,
     error_reporter::ErrorReporter,
     expr::{Expr, ExprType, Literals},
+    instance::Instance,
+    list::List,
+    macros::{MacroExpander, Macros},
+    map::Map,
+    parser::Parser,
+    pattern::{Pattern, PatternType},
     printer::Print,
-    stmt::Stmt,
+    reflection,
+    resolver::Resolver,
+    scanner::Scanner,
+    checker::TypeChecker,
+    stmt::{ClassDeclaration, Decorator, EnumVariant, MatchArm, RequiredMethod, Stmt},
+    traits::Trait,
     token::Token,
-    token_type::TokenType, class::{self, Class},
+    token_type::TokenType, class::Class,
 };
 #[derive(Debug)]
 pub struct RuntimeError {
     token: Rc<Token>,
     message: String,
+    /// note( token, message ) pointing at a related location.
+    note: Option<(Rc<Token>, String)>,
 }
 impl RuntimeError {
     pub fn new(token: Rc<Token>, message: String) -> Self {
-        Self { token, message }
+        Self { token, message, note: None }
     }
+
+    pub fn with_note(mut self, token: Rc<Token>, message: String) -> Self {
+        self.note = Some((token, message));
+        self
+    }
+}
+
+/// Statement stopping statements enclosing it, until reaching the function
+/// or loop it leaves.
+#[derive(Debug)]
+pub enum Flow {
+    Return(Literals),
+    /// Break out of loop with given label, innermost loop if None.
+    Break(Option<Rc<Token>>),
+}
+
+/// Native function call in progress, giving native access to the interpreter.
+struct NativeCall<'i, 'p, T: Print> {
+    interpreter: &'i mut Interpreter<'p, T>,
+    paren: Rc<Token>,
+}
+
+impl<T: Print> NativeContext for NativeCall<'_, '_, T> {
+    fn stringify(&mut self, value: &Literals) -> Result<String, RuntimeError> {
+        self.interpreter.stringify(value)
+    }
+
+    fn error(&self, message: String) -> RuntimeError {
+        RuntimeError::new(self.paren.clone(), message)
+    }
+
+    fn eval(&mut self, source: &str) -> Result<Literals, RuntimeError> {
+        let paren = self.paren.clone();
+        self.interpreter.eval(source, paren)
+    }
+
+    fn parse(&mut self, source: &str) -> Result<Vec<Stmt>, RuntimeError> {
+        let error_reporter = ErrorReporter::new(source, true, self.interpreter.printer).capturing();
+        let ast = reflection::parse_source(source, &error_reporter);
+        match error_reporter.first_error() {
+            Some(error) => Err(source_error(self.paren.clone(), error)),
+            None => Ok(ast),
+        }
+    }
+}
+
+/// Runtime error at call of native for error in source code given to it.
+fn source_error(paren: Rc<Token>, (line, message): (usize, String)) -> RuntimeError {
+    let message = message.trim_end_matches('.');
+    RuntimeError::new(paren, format!("Invalid source at line {line}: {message}"))
+}
+
+/// How names of a pattern get bound to destructured values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Binding {
+    Assign,
+    Variable,
+    Constant,
 }
 
 pub struct Interpreter<'p, T: Print> {
@@ -26,7 +99,21 @@ pub struct Interpreter<'p, T: Print> {
     pub printer: &'p T,
     /// locals: HashMap<Expr.id, depth>
     /// where "depth" is scope to which Expr is resolved.
-    locals: HashMap<usize, usize>
+    locals: HashMap<usize, usize>,
+    /// Ids of instances whose toString() method is running.
+    in_to_string: Vec<usize>,
+    /// Name of class declaring the running function, if any.
+    pub current_class: Option<Rc<Token>>,
+    /// Methods of built-in types: natives and those added with `extend`.
+    builtin_methods: MethodTables,
+    /// Macros declared so far, usable by later runs, such as REPL lines,
+    /// and by source given to eval().
+    macros: Macros,
+    /// Line ends terminate statements, making ';' optional.
+    pub optional_semicolons: bool,
+    /// Undefined globals and reads of unassigned variables are reported
+    /// before running, as with the `"use strict";` pragma.
+    pub strict: bool
 }
 
 impl<'p, T: Print> Interpreter<'p, T> {
@@ -35,6 +122,12 @@ impl<'p, T: Print> Interpreter<'p, T> {
             environment: Environment::default(),
             printer,
             locals: HashMap::new(),
+            in_to_string: vec![],
+            current_class: None,
+            builtin_methods: builtins::native_methods(),
+            macros: Macros::new(),
+            optional_semicolons: false,
+            strict: false,
         };
 
         // Define native function "clock()" to return current time in secs
@@ -42,9 +135,9 @@ impl<'p, T: Print> Interpreter<'p, T> {
             "clock".to_string(),
             Some(Literals::Function(Callable::new_native_fn(
                 Rc::new(
-                    |_args| match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
-                        Ok(d) => Literals::Number(d.as_secs_f64()),
-                        Err(_) => Literals::Nil,
+                    |_ctx, _args| match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+                        Ok(d) => Ok(Literals::Number(d.as_secs_f64())),
+                        Err(_) => Ok(Literals::Nil),
                     },
                 ),
                 0,
@@ -53,38 +146,75 @@ impl<'p, T: Print> Interpreter<'p, T> {
         );
 
         // Define native to_string() function to convert any Lox datatype
-        // into its string representation.
+        // into its string representation, the same one print shows.
         interpreter.environment.define(
             "to_string".to_string(),
             Some(Literals::Function(Callable::new_native_fn(
-                Rc::new(|args| {
-                    let literal = &args[0];
-                    match literal {
-                        Literals::String(_) => literal.clone(),
-                        Literals::Number(n) => Literals::String(n.to_string()),
-                        Literals::Bool(b) => Literals::String(b.to_string()),
-                        Literals::Nil => Literals::String("Nil".to_string()),
-                        Literals::Function(f) => match f {
-                            Callable::Native(native) => {
-                                Literals::String(format!("<native-fn {}()>", native.name))
-                            }
-                            Callable::Foreign(foreign) => {
-                                Literals::String(format!("<fn {}()>", foreign.name))
-                            }
-                            Callable::Class(class_initializer) => {
-                                Literals::String(format!("<class {}>", class_initializer.class.name.lexeme))
-                            },
-                        },
-                        Literals::Instance(instance) => {
-                            Literals::String(format!("<instance {}{{}}>", instance.class.name.lexeme))
-                        },
-                    }
-                }),
+                Rc::new(|ctx, args| Ok(Literals::String(ctx.stringify(&args[0])?))),
                 1,
                 "to_string".to_string(),
             ))),
         );
 
+        // Define natives freeze(), deepFreeze() and isFrozen() to make
+        // instances, lists and maps unmodifiable. freeze() marks only the
+        // value itself, deepFreeze() also every value reachable from it.
+        interpreter.environment.define(
+            "freeze".to_string(),
+            Some(Literals::Function(Callable::new_native_fn(
+                Rc::new(|_ctx, args| {
+                    args[0].freeze(false);
+                    Ok(args[0].clone())
+                }),
+                1,
+                "freeze".to_string(),
+            ))),
+        );
+        interpreter.environment.define(
+            "deepFreeze".to_string(),
+            Some(Literals::Function(Callable::new_native_fn(
+                Rc::new(|_ctx, args| {
+                    args[0].freeze(true);
+                    Ok(args[0].clone())
+                }),
+                1,
+                "deepFreeze".to_string(),
+            ))),
+        );
+        interpreter.environment.define(
+            "isFrozen".to_string(),
+            Some(Literals::Function(Callable::new_native_fn(
+                Rc::new(|_ctx, args| Ok(Literals::Bool(args[0].is_frozen()))),
+                1,
+                "isFrozen".to_string(),
+            ))),
+        );
+
+        // Define natives eval() to run source code in global scope, and
+        // parse() to get its syntax tree as maps and lists.
+        interpreter.environment.define(
+            "eval".to_string(),
+            Some(Literals::Function(Callable::new_native_fn(
+                Rc::new(|ctx, args| match &args[0] {
+                    Literals::String(source) => ctx.eval(source),
+                    _ => Err(ctx.error("Argument of eval() must be a string".to_string())),
+                }),
+                1,
+                "eval".to_string(),
+            ))),
+        );
+        interpreter.environment.define(
+            "parse".to_string(),
+            Some(Literals::Function(Callable::new_native_fn(
+                Rc::new(|ctx, args| match &args[0] {
+                    Literals::String(source) => Ok(reflection::stmts_value(&ctx.parse(source)?)),
+                    _ => Err(ctx.error("Argument of parse() must be a string".to_string())),
+                }),
+                1,
+                "parse".to_string(),
+            ))),
+        );
+
         interpreter
     }
 
@@ -95,6 +225,9 @@ impl<'p, T: Print> Interpreter<'p, T> {
                 Ok(_) => (),
                 Err(e) => {
                     err_reporter.runtime_error(e.token, e.message);
+                    if let Some((token, message)) = e.note {
+                        err_reporter.note_token(token, &message);
+                    }
                     return 70; // 70: An internal software error has been detected
                 }
             }
@@ -102,7 +235,63 @@ impl<'p, T: Print> Interpreter<'p, T> {
         0
     }
 
-    fn execute(&mut self, statement: &Stmt) -> Result<Option<Literals>, RuntimeError> {
+    /// Scans, parses, expands macros, resolves and type checks source before
+    /// it runs, the same way for programs and for source given to eval().
+    /// Returns None once a stage has reported errors.
+    pub fn compile(&mut self, source: &str, error_reporter: &ErrorReporter<'_, 'p, T>) -> Option<Vec<Stmt>> {
+        let mut scanner = Scanner::new(source, error_reporter)
+            .with_optional_semicolons(self.optional_semicolons);
+        scanner.scan_tokens();
+
+        let ast = Parser::new(&scanner.tokens, error_reporter, scanner.optional_semicolons()).parse();
+        if error_reporter.had_error.get() { return None; }
+
+        let ast = MacroExpander::new(error_reporter, &mut self.macros).expand(ast);
+        if error_reporter.had_error.get() { return None; }
+
+        let strict = self.strict;
+        Resolver::new(error_reporter, self).with_strict(strict).resolve(&ast);
+        if error_reporter.had_error.get() { return None; }
+
+        TypeChecker::new(error_reporter).check(&ast);
+        if error_reporter.had_error.get() { return None; }
+        Some(ast)
+    }
+
+    /// Runs source in global scope, returning value of its last statement
+    /// if that is an expression. Errors found before running become runtime
+    /// errors reported at the call of eval(), as do errors while running.
+    fn eval(&mut self, source: &str, paren: Rc<Token>) -> Result<Literals, RuntimeError> {
+        let printer = self.printer;
+        let error_reporter = ErrorReporter::new(source, true, printer).capturing();
+        let Some(ast) = self.compile(source, &error_reporter) else {
+            let error = error_reporter.first_error().expect("Compiling failed with an error");
+            return Err(source_error(paren, error));
+        };
+
+        let enclosing = std::mem::replace(&mut self.environment.scope, self.environment.globals.clone());
+        let result = self.execute_returning_last(&ast);
+        self.environment.scope = enclosing;
+        result.map_err(|err| RuntimeError { token: paren, ..err })
+    }
+
+    fn execute_returning_last(&mut self, stmts: &[Stmt]) -> Result<Literals, RuntimeError> {
+        let Some((last, stmts)) = stmts.split_last() else {
+            return Ok(Literals::Nil);
+        };
+        for stmt in stmts {
+            self.execute(stmt)?;
+        }
+        match last {
+            Stmt::Expression(expr) => self.evaluate(expr),
+            stmt => {
+                self.execute(stmt)?;
+                Ok(Literals::Nil)
+            }
+        }
+    }
+
+    fn execute(&mut self, statement: &Stmt) -> Result<Option<Flow>, RuntimeError> {
         match statement {
             Stmt::Expression(expr) => {
                 self.evaluate(expr)?;
@@ -114,13 +303,36 @@ impl<'p, T: Print> Interpreter<'p, T> {
                 Ok(None)
             }
 
-            Stmt::Class { name, methods } => {
-                self.execute_class_declaration_stmt(name, methods)?;
+            Stmt::Class(class) => {
+                self.execute_class_declaration_stmt(class)?;
                 Ok(None)
             },
 
-            Stmt::Var(name, initializer) => {
-                self.execute_var_declaration_stmt(name.clone(), initializer.as_ref())?;
+            Stmt::Trait { name, methods, required } => {
+                self.execute_trait_declaration_stmt(name, methods, required)?;
+                Ok(None)
+            },
+
+            Stmt::Enum { name, variants } => {
+                self.execute_enum_declaration_stmt(name, variants)?;
+                Ok(None)
+            },
+
+            Stmt::Extend { name, class, methods } => {
+                self.execute_extend_stmt(name, class, methods)?;
+                Ok(None)
+            },
+
+            Stmt::Macro(_) => unreachable!("Macros are expanded before running"),
+
+            Stmt::Var(pattern, _annotation, initializer) => {
+                self.execute_var_declaration_stmt(pattern, initializer.as_ref())?;
+                Ok(None)
+            }
+
+            Stmt::Const(pattern, _annotation, initializer) => {
+                let value = self.evaluate(initializer)?;
+                self.destructure(pattern, value, Binding::Constant)?;
                 Ok(None)
             }
 
@@ -130,22 +342,34 @@ impl<'p, T: Print> Interpreter<'p, T> {
                 self.execute_if_stmt(condition, then_stmt, else_stmt)
             }
 
-            Stmt::While(condition, body) => self.execute_while_statement(condition, body),
+            Stmt::While { label, condition, body, do_while } => {
+                self.execute_while_statement(label.as_ref(), condition, body, *do_while)
+            }
+
+            Stmt::Break { keyword: _, label } => Ok(Some(Flow::Break(label.clone()))),
+
+            Stmt::Match {
+                keyword,
+                subject,
+                arms,
+            } => self.execute_match_stmt(keyword, subject, arms),
 
             Stmt::Function {
                 name,
                 params,
                 body: _,
+                decorators,
+                signature: _,
             } => {
-                self.execute_fun_declaration_stmt(name.clone(), statement, params.len());
+                self.execute_fun_declaration_stmt(name.clone(), statement, params.len(), decorators)?;
                 Ok(None)
             }
             Stmt::Return {
                 return_keyword,
                 expression,
-            } => Ok(Some(
+            } => Ok(Some(Flow::Return(
                 self.execute_return_stmt(return_keyword.clone(), expression)?,
-            ))
+            )))
         }
     }
 
@@ -157,9 +381,13 @@ impl<'p, T: Print> Interpreter<'p, T> {
             ExprType::Literal(literal) => Ok(literal.clone()),
             ExprType::Unary(op, right) => self.interpret_unary(op.clone(), right),
             ExprType::Variable(variable) => self.interpret_variable(variable.clone(), expr),
+            ExprType::This(keyword) => self.lookup_variable(keyword.clone(), expr),
             ExprType::Assign(var_name, rvalue) => {
                 self.execute_assign_expr(var_name.clone(), rvalue, expr)
             }
+            ExprType::AssignPattern(target, rvalue) => {
+                self.execute_assign_pattern_expr(target, rvalue)
+            }
             ExprType::Logical(left, op, right) => self.interpret_logical(op.clone(), left, right),
             ExprType::Call {
                 callee,
@@ -168,6 +396,25 @@ impl<'p, T: Print> Interpreter<'p, T> {
             } => self.interpret_call(callee, paren, arguments),
             ExprType::Get { object, property } => self.interpret_get_accessor(object, property),
             ExprType::Set { object, property, value } => self.interpret_set_accessor(object, property, value),
+            ExprType::List { bracket: _, elements } => self.interpret_list(elements),
+            ExprType::Tuple { paren: _, elements } => self.interpret_tuple(elements),
+            ExprType::Map { brace: _, entries } => self.interpret_map(entries),
+            ExprType::Index { object, bracket, index } => self.interpret_index(object, bracket, index),
+            ExprType::SetIndex { object, bracket, index, value } => {
+                self.interpret_set_index(object, bracket, index, value)
+            }
+            ExprType::Block { brace: _, statements, value } => {
+                self.execute_block_value(statements, value.as_deref())
+            }
+            ExprType::If { keyword: _, condition, then_branch, else_branch } => {
+                if Self::into_bool(&self.evaluate(condition)?) {
+                    self.evaluate(then_branch)
+                } else if let Some(else_branch) = else_branch {
+                    self.evaluate(else_branch)
+                } else {
+                    Ok(Literals::Nil)
+                }
+            }
         }
     }
 
@@ -176,6 +423,10 @@ impl<'p, T: Print> Interpreter<'p, T> {
         match op.token_type {
             TokenType::Minus => match right {
                 Literals::Number(n) => Ok(Literals::Number(-n)),
+                // `-a` dispatches to `a.__neg__()`.
+                Literals::Instance(_) => self
+                    .call_special_method(&op, &right, "__neg__", vec![])?
+                    .ok_or_else(|| RuntimeError::new(op, "Operand must be number".into())),
                 _ => Err(RuntimeError::new(op, "Operand must be number".into())),
             },
             TokenType::Bang => {
@@ -188,6 +439,7 @@ impl<'p, T: Print> Interpreter<'p, T> {
                 // }
                 Ok(Literals::Bool(!Self::into_bool(&right)))
             }
+            TokenType::Typeof => Ok(Literals::String(builtins::type_of(&right).to_string())),
             _ => unreachable!(),
         }
     }
@@ -209,10 +461,7 @@ impl<'p, T: Print> Interpreter<'p, T> {
         var_name: Rc<Token>,
         var_expr: &Expr,
     ) -> Result<Literals, RuntimeError> {
-        let x = self.lookup_variable(var_name, var_expr);
-        dbg!(&x);
-        x
-        // self.environment.get(var_name)
+        self.lookup_variable(var_name, var_expr)
     }
 
     fn lookup_variable(&self, var_name: Rc<Token>, expr: &Expr) -> Result<Literals, RuntimeError> {
@@ -223,6 +472,27 @@ impl<'p, T: Print> Interpreter<'p, T> {
         }
     }
 
+    /// Binary operators on instances dispatch to special methods of their class:
+    ///
+    /// | operator | method    | reflected  |
+    /// |----------|-----------|------------|
+    /// | `+`      | `__add__` | `__radd__` |
+    /// | `-`      | `__sub__` | `__rsub__` |
+    /// | `*`      | `__mul__` | `__rmul__` |
+    /// | `/`      | `__div__` | `__rdiv__` |
+    /// | `<`      | `__lt__`  | `__gt__`   |
+    /// | `<=`     | `__le__`  | `__ge__`   |
+    /// | `>`      | `__gt__`  | `__lt__`   |
+    /// | `>=`     | `__ge__`  | `__le__`   |
+    /// | `==`     | `__eq__`  | `__eq__`   |
+    /// | `!=`     | `__eq__`  | `__eq__`   |
+    ///
+    /// Dispatch order for `a op b`:
+    /// 1. `a.method(b)` if `a` is an instance whose class defines the method.
+    /// 2. `b.reflected(a)` if `b` is an instance whose class defines the reflected method.
+    /// 3. Built-in behaviour of the operator, e.g. identity equality of instances.
+    ///
+    /// `!=` negates truthiness of the value returned by `__eq__`.
     fn interpret_binary(
         &mut self,
         op: Rc<Token>,
@@ -232,6 +502,19 @@ impl<'p, T: Print> Interpreter<'p, T> {
         let left = self.evaluate(left)?;
         let right = self.evaluate(right)?;
 
+        match op.token_type {
+            TokenType::Is => return Self::is_instance_of(&op, &left, &right).map(Literals::Bool),
+            TokenType::In => return Self::contains(&op, &left, &right).map(Literals::Bool),
+            _ => {}
+        }
+
+        if let Some(result) = self.overloaded_binary(&op, &left, &right)? {
+            if op.token_type == TokenType::BangEqual {
+                return Ok(Literals::Bool(!Self::into_bool(&result)));
+            }
+            return Ok(result);
+        }
+
         match op.token_type {
             TokenType::Plus => {
                 if let Literals::Number(a) = left {
@@ -333,6 +616,91 @@ impl<'p, T: Print> Interpreter<'p, T> {
         }
     }
 
+    /// `value is Type` tests whether value is an instance of class or a
+    /// variant of enum. Classes have no superclasses, so class chain of an
+    /// instance is only its own class.
+    fn is_instance_of(op: &Rc<Token>, value: &Literals, target: &Literals) -> Result<bool, RuntimeError> {
+        match (value, target) {
+            (Literals::Instance(instance), Literals::Function(Callable::Class(class))) => {
+                Ok(Rc::ptr_eq(&instance.class, &class.class))
+            }
+            (Literals::Variant(variant), Literals::Enum(enumeration)) => Ok(variant.is_of(enumeration)),
+            (_, Literals::Function(Callable::Class(_)) | Literals::Enum(_)) => Ok(false),
+            _ => Err(RuntimeError::new(
+                op.clone(),
+                format!("Right operand of 'is' must be a class or enum, got {}", target.repr())
+            )),
+        }
+    }
+
+    /// `key in collection` tests keys of maps, elements of lists and tuples,
+    /// substrings of strings and fields of instances.
+    fn contains(op: &Rc<Token>, key: &Literals, collection: &Literals) -> Result<bool, RuntimeError> {
+        match (key, collection) {
+            (_, Literals::Map(map)) => Ok(map.lookup(key).is_some()),
+            (_, Literals::List(list)) => Ok(list.elements.borrow().contains(key)),
+            (_, Literals::Tuple(elements)) => Ok(elements.contains(key)),
+            (Literals::String(key), Literals::String(string)) => Ok(string.contains(key.as_str())),
+            (Literals::String(key), Literals::Instance(instance)) => {
+                Ok(instance.fields.borrow().contains_key(key))
+            }
+            (_, Literals::String(_) | Literals::Instance(_)) => Err(RuntimeError::new(
+                op.clone(),
+                format!("Left operand of 'in' must be a string, got {}", key.repr())
+            )),
+            _ => Err(RuntimeError::new(
+                op.clone(),
+                format!("Right operand of 'in' must be a map, list, tuple, string or instance, got {}", collection.repr())
+            )),
+        }
+    }
+
+    fn overloaded_binary(
+        &mut self,
+        op: &Rc<Token>,
+        left: &Literals,
+        right: &Literals,
+    ) -> Result<Option<Literals>, RuntimeError> {
+        let (method, reflected) = match op.token_type {
+            TokenType::Plus => ("__add__", "__radd__"),
+            TokenType::Minus => ("__sub__", "__rsub__"),
+            TokenType::Star => ("__mul__", "__rmul__"),
+            TokenType::Slash => ("__div__", "__rdiv__"),
+            TokenType::Less => ("__lt__", "__gt__"),
+            TokenType::LessEqual => ("__le__", "__ge__"),
+            TokenType::Greater => ("__gt__", "__lt__"),
+            TokenType::GreaterEqual => ("__ge__", "__le__"),
+            TokenType::EqualEqual | TokenType::BangEqual => ("__eq__", "__eq__"),
+            _ => unreachable!(),
+        };
+        if let Some(result) = self.call_special_method(op, left, method, vec![right.clone()])? {
+            return Ok(Some(result));
+        }
+        self.call_special_method(op, right, reflected, vec![left.clone()])
+    }
+
+    /// Calls special method of receiver's class.
+    /// Returns None if receiver is not an instance or its class lacks the method.
+    fn call_special_method(
+        &mut self,
+        op: &Rc<Token>,
+        receiver: &Literals,
+        method: &str,
+        args: Vec<Literals>,
+    ) -> Result<Option<Literals>, RuntimeError> {
+        let Literals::Instance(instance) = receiver else {
+            return Ok(None);
+        };
+        let Some(method) = instance.class.find_method(method) else {
+            return Ok(None);
+        };
+        method
+            .bind(receiver.clone())
+            .call(self, args)
+            .map(Some)
+            .map_err(|err| RuntimeError { token: op.clone(), ..err })
+    }
+
     fn interpret_logical(
         &mut self,
         op: Rc<Token>,
@@ -362,11 +730,49 @@ impl<'p, T: Print> Interpreter<'p, T> {
         paren: &Rc<Token>,
         args: &[Expr],
     ) -> Result<Literals, RuntimeError> {
-        let callee = self.evaluate(callee)?;
+        // Getter runs as soon as its property is accessed, so `rect.area()`
+        // calls value returned by getter. Remember the getter to tell
+        // why call fails if that value isn't callable.
+        let mut getter = None;
+        let callee = match &callee.expr_type {
+            ExprType::Get { object: object_expr, property } => {
+                let accessor = self.accessor(object_expr);
+                let object = self.evaluate(object_expr)?;
+                if let Literals::Instance(instance) = &object {
+                    if !instance.fields.borrow().contains_key(&property.lexeme)
+                        && instance.class.find_getter(&property.lexeme).is_some()
+                    {
+                        getter = Some((property.clone(), instance.class.name.clone()));
+                    }
+                }
+                self.get_property(object, property, accessor.as_ref())?
+            }
+            _ => self.evaluate(callee)?,
+        };
         let mut arguments = vec![];
         for arg in args {
             arguments.push(self.evaluate(arg)?);
         }
+        if let (false, Some((property, class_name))) = (matches!(callee, Literals::Function(_)), getter) {
+            return Err(RuntimeError::new(
+                property.clone(),
+                format!(
+                    "Can't call getter '{}' of class '{}', access it without '()'",
+                    property.lexeme, class_name.lexeme
+                ),
+            ));
+        }
+        self.call_value(callee, arguments, paren)
+    }
+
+    /// Calls function, class or variant constructor value with arguments,
+    /// reporting errors at paren.
+    pub fn call_value(
+        &mut self,
+        callee: Literals,
+        arguments: Vec<Literals>,
+        paren: &Rc<Token>,
+    ) -> Result<Literals, RuntimeError> {
         if let Literals::Function(Callable::Native(function)) = callee {
             if arguments.len() != function.arity {
                 return Err(RuntimeError::new(
@@ -378,16 +784,23 @@ impl<'p, T: Print> Interpreter<'p, T> {
                     ),
                 ));
             }
-            Ok((function.call)(arguments))
+            let mut ctx = NativeCall { interpreter: self, paren: paren.clone() };
+            (function.call)(&mut ctx, arguments)
         } else if let Literals::Function(Callable::Foreign(function)) = callee {
             match function.call(self, arguments) {
                 Ok(return_val) => Ok(return_val),
-                Err(err) => Err(RuntimeError::new(paren.clone(), err.message)),
+                Err(err) => Err(RuntimeError { token: paren.clone(), ..err }),
             }
         } else if let Literals::Function(
             Callable::Class(class_init)
         ) = callee {
-            class_init.call(self, vec![])
+            class_init.call(self, arguments)
+                .map_err(|err| RuntimeError { token: paren.clone(), ..err })
+        } else if let Literals::Function(Callable::Variant(constructor)) = callee {
+            constructor.call(arguments)
+                .map_err(|err| RuntimeError::new(paren.clone(), err.message))
+        } else if let Literals::Function(Callable::Decorated(decorated)) = callee {
+            decorated.call(self, arguments, paren)
         } else {
             Err(RuntimeError::new(
                 paren.clone(),
@@ -401,11 +814,46 @@ impl<'p, T: Print> Interpreter<'p, T> {
         object: &Expr,
         property: &Rc<Token>
     ) -> Result<Literals, RuntimeError>  {
+        let accessor = self.accessor(object);
         let object = self.evaluate(object)?;
-        if let Literals::Instance(object) = object {
-            return object.get(property.clone());
+        self.get_property(object, property, accessor.as_ref())
+    }
+
+    /// Class allowed to access private members of object: the class declaring
+    /// the running function, when object is 'this'.
+    fn accessor(&self, object: &Expr) -> Option<Rc<Token>> {
+        match object.expr_type {
+            ExprType::This(_) => self.current_class.clone(),
+            _ => None,
+        }
+    }
+
+    fn get_property(
+        &mut self,
+        object: Literals,
+        property: &Rc<Token>,
+        accessor: Option<&Rc<Token>>
+    ) -> Result<Literals, RuntimeError> {
+        match object {
+            Literals::Instance(object) => object.get(property.clone(), self, accessor),
+            Literals::Function(Callable::Class(class)) => class.get(property.clone(), accessor),
+            Literals::Enum(enumeration) => enumeration.get(property.clone()),
+            Literals::Variant(variant) => variant.get(property.clone()),
+            value => {
+                let Some(type_name) = builtins::type_name(&value) else {
+                    return Err(RuntimeError::new(property.clone(), "Only instances have property".to_string()));
+                };
+                match self.builtin_methods[type_name].get(&property.lexeme) {
+                    Some(Callable::Native(method)) => Ok(Literals::Function(Callable::Native(method.bind(value)))),
+                    Some(Callable::Foreign(method)) => Ok(Literals::Function(Callable::Foreign(method.bind(value)))),
+                    Some(_) => unreachable!("Built-in types only have native and extension methods"),
+                    None => Err(RuntimeError::new(
+                        property.clone(),
+                        format!("Undefined method '{}' of type '{}'", property.lexeme, type_name)
+                    )),
+                }
+            }
         }
-        Err(RuntimeError::new(property.clone(), "Only instances have property".to_string()))
     }
 
     fn interpret_set_accessor(
@@ -414,11 +862,20 @@ impl<'p, T: Print> Interpreter<'p, T> {
         property: &Rc<Token>,
         value: &Expr
     ) -> Result<Literals, RuntimeError>  {
+        let accessor = self.accessor(object);
         let object = self.evaluate(object)?;
-        if let Literals::Instance(mut object) = object {
+        if let Literals::Instance(object) = object {
             let value = self.evaluate(value)?;
-            object.set(property.clone(), value.clone());
-            // self.environment.a
+            if let Some(setter) = object.class.find_setter(&property.lexeme) {
+                object.class.check_access(property, accessor.as_ref())?;
+                setter.bind(Literals::Instance(object.clone())).call(self, vec![value.clone()])?;
+            } else {
+                object.set(property.clone(), value.clone(), accessor.as_ref())?;
+            }
+            Ok(value)
+        } else if let Literals::Function(Callable::Class(class)) = object {
+            let value = self.evaluate(value)?;
+            class.set(property.clone(), value.clone(), accessor.as_ref())?;
             Ok(value)
         } else {
             Err(RuntimeError::new(
@@ -428,12 +885,79 @@ impl<'p, T: Print> Interpreter<'p, T> {
         }
     }
 
+    fn interpret_list(&mut self, elements: &[Expr]) -> Result<Literals, RuntimeError> {
+        let mut values = vec![];
+        for element in elements {
+            values.push(self.evaluate(element)?);
+        }
+        Ok(Literals::List(List::new(values)))
+    }
+
+    fn interpret_tuple(&mut self, elements: &[Expr]) -> Result<Literals, RuntimeError> {
+        let mut values = vec![];
+        for element in elements {
+            values.push(self.evaluate(element)?);
+        }
+        Ok(Literals::Tuple(Rc::new(values)))
+    }
+
+    fn interpret_map(&mut self, entries: &[(Expr, Expr)]) -> Result<Literals, RuntimeError> {
+        let mut values = vec![];
+        for (key, value) in entries {
+            values.push((self.evaluate(key)?, self.evaluate(value)?));
+        }
+        Ok(Literals::Map(Map::new(values)))
+    }
+
+    fn interpret_index(
+        &mut self,
+        object: &Expr,
+        bracket: &Rc<Token>,
+        index: &Expr
+    ) -> Result<Literals, RuntimeError> {
+        let object = self.evaluate(object)?;
+        let index = self.evaluate(index)?;
+        match object {
+            Literals::List(list) => list.get(bracket.clone(), &index),
+            Literals::Map(map) => map.get(bracket.clone(), &index),
+            Literals::Tuple(elements) => {
+                // Tuples share index rules of lists, without being modifiable.
+                List::new(elements.to_vec()).get(bracket.clone(), &index)
+            }
+            _ => Err(RuntimeError::new(
+                bracket.clone(),
+                "Can only index lists, tuples and maps".to_string()
+            )),
+        }
+    }
+
+    fn interpret_set_index(
+        &mut self,
+        object: &Expr,
+        bracket: &Rc<Token>,
+        index: &Expr,
+        value: &Expr
+    ) -> Result<Literals, RuntimeError> {
+        let object = self.evaluate(object)?;
+        let index = self.evaluate(index)?;
+        let value = self.evaluate(value)?;
+        match object {
+            Literals::List(list) => list.set(bracket.clone(), &index, value.clone())?,
+            Literals::Map(map) => map.set(bracket.clone(), index, value.clone())?,
+            _ => return Err(RuntimeError::new(
+                bracket.clone(),
+                "Can only set elements of lists and maps".to_string()
+            )),
+        }
+        Ok(value)
+    }
+
     pub fn execute_block(
         &mut self,
         stmts: &[Stmt],
 
         with_new_scope: bool,
-    ) -> Result<Option<Literals>, RuntimeError> {
+    ) -> Result<Option<Flow>, RuntimeError> {
         if with_new_scope {
             self.environment.create_new_scope();
         }
@@ -450,12 +974,28 @@ impl<'p, T: Print> Interpreter<'p, T> {
         Ok(return_value)
     }
 
+    /// Runs statements of block expression in new scope and evaluates its
+    /// trailing expression, nil if there is none. Resolver rejects 'return'
+    /// inside block expressions, so statements never return a value.
+    fn execute_block_value(&mut self, stmts: &[Stmt], value: Option<&Expr>) -> Result<Literals, RuntimeError> {
+        self.environment.create_new_scope();
+        for stmt in stmts {
+            self.execute(stmt)?;
+        }
+        let value = match value {
+            Some(value) => self.evaluate(value)?,
+            None => Literals::Nil,
+        };
+        self.environment.end_latest_scope();
+        Ok(value)
+    }
+
     fn execute_if_stmt(
         &mut self,
         condition: &Expr,
         then_stmt: &Stmt,
         else_statement: &Option<Stmt>,
-    ) -> Result<Option<Literals>, RuntimeError> {
+    ) -> Result<Option<Flow>, RuntimeError> {
         if Self::into_bool(&self.evaluate(condition)?) {
             return self.execute(then_stmt);
         } else if let Some(else_stmt) = else_statement {
@@ -466,73 +1006,516 @@ impl<'p, T: Print> Interpreter<'p, T> {
 
     fn execute_print_stmt(&mut self, expr: &Expr) -> Result<(), RuntimeError> {
         let value = self.evaluate(expr)?;
-        // TODO: Define and use native to_string() function to get printable strings.
-        match value {
-            Literals::Nil => self.printer.print(&"Nil"),
-            Literals::String(s) => self.printer.print(&s),
-            Literals::Number(n) => self.printer.print(&n),
-            Literals::Bool(b) => self.printer.print(&b),
-            Literals::Function(Callable::Class(_)) => self.printer.print(&"<class>"),
-            Literals::Function(_) => self.printer.print(&"<fn>"),
-            Literals::Instance(_) => self.printer.print(&"<instance>"),
-        }
+        let s = self.stringify(&value)?;
+        self.printer.print(&s);
         Ok(())
     }
 
+    /// String representation of value, using `toString()` method of instances
+    /// whose class defines one.
+    pub fn stringify(&mut self, value: &Literals) -> Result<String, RuntimeError> {
+        value.stringify(&mut |instance| self.call_to_string(instance))
+    }
+
+    fn call_to_string(&mut self, instance: &Instance) -> Result<Option<String>, RuntimeError> {
+        let Some(to_string) = instance.class.find_method("toString") else {
+            return Ok(None);
+        };
+        // toString() printing its own instance falls back to default representation.
+        if self.in_to_string.contains(&instance.id()) {
+            return Ok(None);
+        }
+        self.in_to_string.push(instance.id());
+        let result = to_string.bind(Literals::Instance(instance.clone())).call(self, vec![]);
+        self.in_to_string.pop();
+        match result? {
+            Literals::String(s) => Ok(Some(s)),
+            other => Ok(Some(self.stringify(&other)?)),
+        }
+    }
+
+    /// Runs loop until its condition fails, or a statement in body breaks
+    /// out of it or returns. Break of another label leaves this loop too,
+    /// on its way to the enclosing loop with that label.
     fn execute_while_statement(
         &mut self,
+        label: Option<&Rc<Token>>,
         condition: &Expr,
         body: &Stmt,
-    ) -> Result<Option<Literals>, RuntimeError> {
-        while Self::into_bool(&self.evaluate(condition)?) {
-            let return_value = self.execute(body)?;
-            if return_value.is_some() {
-                return Ok(return_value);
+        do_while: bool,
+    ) -> Result<Option<Flow>, RuntimeError> {
+        // do-while loops check condition only after first iteration.
+        let mut skip_condition = do_while;
+        while std::mem::take(&mut skip_condition) || Self::into_bool(&self.evaluate(condition)?) {
+            match self.execute(body)? {
+                None => (),
+                Some(Flow::Break(None)) => break,
+                Some(Flow::Break(Some(target)))
+                    if label.is_some_and(|label| label.lexeme == target.lexeme) => break,
+                flow => return Ok(flow),
             }
         }
         Ok(None)
     }
 
-    fn execute_class_declaration_stmt(
-        &mut self, name: &Rc<Token>, methods: &[Stmt]
-    ) -> Result<(), RuntimeError> {
-        self.environment.define(name.lexeme.to_string(), None);
-        let class = Rc::new(Class::new(name.clone()));
+    fn execute_match_stmt(
+        &mut self,
+        keyword: &Rc<Token>,
+        subject: &Expr,
+        arms: &[MatchArm],
+    ) -> Result<Option<Flow>, RuntimeError> {
+        let value = self.evaluate(subject)?;
+        for arm in arms {
+            // Every arm binds its pattern in a fresh scope, which is dropped
+            // together with any partial bindings if the arm does not match.
+            self.environment.create_new_scope();
+            let result = match self.match_arm(arm, &value) {
+                Ok(true) => Some(self.execute(&arm.body)),
+                Ok(false) => None,
+                Err(e) => Some(Err(e)),
+            };
+            self.environment.end_latest_scope();
+            if let Some(result) = result {
+                return result;
+            }
+        }
+        Err(RuntimeError::new(
+            keyword.clone(),
+            format!("No match arm for value {}", value.repr()),
+        ))
+    }
+
+    fn match_arm(&mut self, arm: &MatchArm, value: &Literals) -> Result<bool, RuntimeError> {
+        if !self.match_pattern(&arm.pattern, value)? {
+            return Ok(false);
+        }
+        match &arm.guard {
+            Some(guard) => Ok(Self::into_bool(&self.evaluate(guard)?)),
+            None => Ok(true),
+        }
+    }
+
+    /// Checks whether value matches pattern, defining bound names in
+    /// current scope along the way.
+    fn match_pattern(&mut self, pattern: &Pattern, value: &Literals) -> Result<bool, RuntimeError> {
+        match &pattern.pattern_type {
+            PatternType::Binding(name) => {
+                self.environment.define(name.lexeme.to_string(), Some(value.clone()));
+                Ok(true)
+            }
+            PatternType::Wildcard(_) => Ok(true),
+            PatternType::Literal(_, literal) => Ok(literal == value),
+            PatternType::Alternatives(alternatives) => {
+                for alternative in alternatives {
+                    if self.match_pattern(alternative, value)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            PatternType::List(_, elements) => {
+                let Literals::List(list) = value else {
+                    return Ok(false);
+                };
+                let values = list.elements.borrow().clone();
+                self.match_elements(elements, &values)
+            }
+            PatternType::Tuple(_, elements) => {
+                let Literals::Tuple(values) = value else {
+                    return Ok(false);
+                };
+                self.match_elements(elements, values)
+            }
+            PatternType::Fields(_, fields) => {
+                if !matches!(value, Literals::Instance(_) | Literals::Map(_)) {
+                    return Ok(false);
+                }
+                self.match_fields(fields, value)
+            }
+            PatternType::Variant { name, enumeration, variant, payload } => {
+                let Literals::Variant(value) = value else {
+                    return Ok(false);
+                };
+                let Literals::Enum(enumeration) = self.evaluate(enumeration)? else {
+                    return Err(RuntimeError::new(
+                        name.clone(),
+                        format!("'{}' in pattern is not an enum", name.lexeme),
+                    ));
+                };
+                if !value.is_variant_of(&enumeration, &variant.lexeme) {
+                    return Ok(false);
+                }
+                match payload {
+                    Some(payload) => self.match_elements(payload, &value.values),
+                    None => Ok(true),
+                }
+            }
+            PatternType::Instance { name, class, fields } => {
+                let Literals::Instance(instance) = value else {
+                    return Ok(false);
+                };
+                let Literals::Function(Callable::Class(class_initializer)) = self.evaluate(class)? else {
+                    return Err(RuntimeError::new(
+                        name.clone(),
+                        format!("'{}' in pattern is not a class", name.lexeme),
+                    ));
+                };
+                if !Rc::ptr_eq(&instance.class, &class_initializer.class) {
+                    return Ok(false);
+                }
+                self.match_fields(fields, value)
+            }
+        }
+    }
+
+    fn match_elements(&mut self, elements: &[Pattern], values: &[Literals]) -> Result<bool, RuntimeError> {
+        if elements.len() != values.len() {
+            return Ok(false);
+        }
+        for (element, value) in elements.iter().zip(values) {
+            if !self.match_pattern(element, value)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn match_fields(&mut self, fields: &[Pattern], object: &Literals) -> Result<bool, RuntimeError> {
+        for field in fields {
+            let Some(value) = self.read_field(object, field.token())? else {
+                return Ok(false);
+            };
+            if !self.match_pattern(field, &value)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Reads field of an instance or string key of a map for field patterns.
+    fn read_field(&mut self, object: &Literals, name: Rc<Token>) -> Result<Option<Literals>, RuntimeError> {
+        match object {
+            Literals::Instance(instance) if instance.has(&name.lexeme) => {
+                Ok(Some(instance.get(name, self, None)?))
+            }
+            Literals::Map(map) => Ok(map.lookup(&Literals::String(name.lexeme.to_string()))),
+            _ => Ok(None)
+        }
+    }
+
+    /// Creates methods closing over current scope. Only instance methods
+    /// named "init" are initializers. Decorators of methods are applied
+    /// here, once for all instances of the class.
+    fn method_table(
+        &mut self,
+        methods: &[Stmt],
+        with_initializer: bool,
+        class: Option<&Rc<Token>>
+    ) -> Result<HashMap<String, ForeignFn>, RuntimeError> {
+        let mut table = HashMap::new();
+        for method in methods {
+            let Stmt::Function { name, params: _, body: _, decorators, signature: _ } = method else {
+                unreachable!("Class body only contains method declarations")
+            };
+            let is_initializer = with_initializer && name.lexeme == "init";
+            let mut function = ForeignFn::new_method(
+                Rc::new(method.clone()),
+                self.environment.scope.clone(),
+                is_initializer,
+                class.cloned()
+            );
+            if !decorators.is_empty() {
+                let decorators = self.evaluate_decorators(decorators)?;
+                let (method, this_scope) = function.bind_shared();
+                let decorated = self.decorate(Literals::Function(Callable::Foreign(method)), &decorators)?;
+                function.decorated = Some(DecoratedFn::new(decorated, this_scope));
+            }
+            table.insert(name.lexeme.to_string(), function);
+        }
+        Ok(table)
+    }
+
+    fn evaluate_decorators(&mut self, decorators: &[Decorator]) -> Result<Vec<(Rc<Token>, Literals)>, RuntimeError> {
+        decorators.iter()
+        .map(|decorator| Ok((decorator.at.clone(), self.evaluate(&decorator.expression)?)))
+        .collect()
+    }
+
+    /// Replaces function with result of calling decorators on it, starting
+    /// with the decorator closest to the declaration.
+    fn decorate(
+        &mut self,
+        function: Literals,
+        decorators: &[(Rc<Token>, Literals)]
+    ) -> Result<Literals, RuntimeError> {
+        let mut function = function;
+        for (at, decorator) in decorators.iter().rev() {
+            function = self.call_value(decorator.clone(), vec![function], at)?;
+        }
+        Ok(function)
+    }
+
+    fn execute_class_declaration_stmt(&mut self, declaration: &ClassDeclaration) -> Result<(), RuntimeError> {
+        let name = &declaration.name;
+        self.environment.define_declared(name, None)?;
+        let mut methods = self.method_table(&declaration.methods, true, Some(name))?;
+        self.compose_traits(declaration, &mut methods)?;
+        let class = Rc::new(Class::new(
+            name.clone(),
+            methods,
+            self.method_table(&declaration.getters, false, Some(name))?,
+            self.method_table(&declaration.setters, false, Some(name))?,
+            self.method_table(&declaration.class_methods, false, Some(name))?
+        ));
         self.environment.assign_at(
             0, name.clone(), 
-            Literals::Function(Callable::new_class_initializer(class))
+            Literals::Function(Callable::new_class_initializer(class.clone()))
+        )?;
+        // Static fields are initialized once the class exists, so initializers can refer to it.
+        for field in &declaration.static_fields {
+            let value = match &field.initializer {
+                Some(initializer) => self.evaluate(initializer)?,
+                None => Literals::Nil,
+            };
+            class.static_fields.borrow_mut().insert(field.name.lexeme.to_string(), value);
+        }
+        Ok(())
+    }
+
+    /// Adds methods of traits used by the class to its methods. Methods of the
+    /// class itself take precedence, while the same method coming from two
+    /// traits is a conflict. Afterwards every method required by the traits
+    /// must be present.
+    fn compose_traits(
+        &mut self,
+        declaration: &ClassDeclaration,
+        methods: &mut HashMap<String, ForeignFn>,
+    ) -> Result<(), RuntimeError> {
+        let class_name = &declaration.name;
+        let mut traits = vec![];
+        for trait_expr in &declaration.traits {
+            let ExprType::Variable(trait_name) = &trait_expr.expr_type else {
+                unreachable!("Traits are referred to by name")
+            };
+            let Literals::Trait(t) = self.evaluate(trait_expr)? else {
+                return Err(RuntimeError::new(
+                    trait_name.clone(),
+                    format!("'{}' is not a trait", trait_name.lexeme),
+                ));
+            };
+            traits.push(t);
+        }
+
+        // provided_by: HashMap<method name, trait providing it>
+        let mut provided_by: HashMap<&str, &Trait> = HashMap::new();
+        for t in &traits {
+            // Sorted, so the same conflict is reported on every run.
+            let mut trait_methods: Vec<_> = t.methods.iter().collect();
+            trait_methods.sort_by_key(|(method_name, _)| *method_name);
+            for (method_name, method) in trait_methods {
+                if methods.contains_key(method_name) && !provided_by.contains_key(method_name.as_str()) {
+                    continue;
+                }
+                if let Some(other) = provided_by.get(method_name.as_str()) {
+                    return Err(RuntimeError::new(
+                        class_name.clone(),
+                        format!(
+                            "Method '{}' of class '{}' is provided by both traits '{}' and '{}'",
+                            method_name, class_name.lexeme, other.name.lexeme, t.name.lexeme
+                        ),
+                    ).with_note(
+                        t.name.clone(),
+                        format!("Define '{}' in class '{}' to resolve the conflict", method_name, class_name.lexeme),
+                    ));
+                }
+                provided_by.insert(method_name, t);
+                methods.insert(method_name.to_string(), method.clone());
+            }
+        }
+
+        for t in &traits {
+            for required in &t.required {
+                let Some(method) = methods.get(&required.name.lexeme) else {
+                    return Err(RuntimeError::new(
+                        class_name.clone(),
+                        format!(
+                            "Class '{}' must define method '{}' required by trait '{}'",
+                            class_name.lexeme, required.name.lexeme, t.name.lexeme
+                        ),
+                    ).with_note(required.name.clone(), format!("'{}' is required here", required.name.lexeme)));
+                };
+                if method.arity != required.params.len() {
+                    return Err(RuntimeError::new(
+                        class_name.clone(),
+                        format!(
+                            "Method '{}' of class '{}' must take {} parameters as required by trait '{}'",
+                            required.name.lexeme, class_name.lexeme, required.params.len(), t.name.lexeme
+                        ),
+                    ).with_note(required.name.clone(), format!("'{}' is required here", required.name.lexeme)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn execute_trait_declaration_stmt(
+        &mut self, name: &Rc<Token>, methods: &[Stmt], required: &[RequiredMethod]
+    ) -> Result<(), RuntimeError> {
+        self.environment.define_declared(name, None)?;
+        let t = Trait::new(name.clone(), self.method_table(methods, false, None)?, required.to_vec());
+        self.environment.assign_at(0, name.clone(), Literals::Trait(Rc::new(t)))?;
+        Ok(())
+    }
+
+    fn execute_extend_stmt(
+        &mut self, name: &Rc<Token>, class: &Expr, methods: &[Stmt]
+    ) -> Result<(), RuntimeError> {
+        let table = self.method_table(methods, false, None)?;
+        let methods = methods.iter().map(|method| {
+            let Stmt::Function { name, params: _, body: _, decorators: _, signature: _ } = method else {
+                unreachable!("Extension body only contains method declarations")
+            };
+            (name.clone(), table[&name.lexeme].clone())
+        });
+
+        if let Some(type_name) = builtins::TYPE_NAMES.iter().find(|t| **t == name.lexeme) {
+            let builtin_methods = self.builtin_methods.get_mut(type_name).unwrap();
+            let methods: Vec<_> = methods.collect();
+            // Nothing is added if any method already exists.
+            if let Some((method_name, _)) = methods.iter().find(|(n, _)| builtin_methods.contains_key(&n.lexeme)) {
+                return Err(RuntimeError::new(
+                    method_name.clone(),
+                    format!("Type '{}' already has method '{}'", type_name, method_name.lexeme)
+                ));
+            }
+            for (method_name, method) in methods {
+                builtin_methods.insert(method_name.lexeme.to_string(), Callable::Foreign(method));
+            }
+            return Ok(());
+        }
+        match self.evaluate(class)? {
+            Literals::Function(Callable::Class(class)) => class.class.extend(methods.collect()),
+            _ => Err(RuntimeError::new(
+                name.clone(),
+                format!("Can only extend classes and built-in types, '{}' is not a class", name.lexeme)
+            )),
+        }
+    }
+
+    fn execute_enum_declaration_stmt(
+        &mut self, name: &Rc<Token>, variants: &[EnumVariant]
+    ) -> Result<(), RuntimeError> {
+        self.environment.define_declared(name, None)?;
+        let enumeration = Rc::new(Enum::new(name.clone(), variants));
+        self.environment.assign_at(
+            0, name.clone(),
+            Literals::Enum(enumeration)
         )?;
         Ok(())
     }
 
     fn execute_var_declaration_stmt(
         &mut self,
-        name: Rc<Token>,
+        pattern: &Pattern,
         expr: Option<&Expr>,
     ) -> Result<(), RuntimeError> {
-        let value = if expr.is_some() {
-            Some(self.evaluate(expr.unwrap())?)
-        } else {
-            None
+        let Some(expr) = expr else {
+            // Parser allows uninitialized declaration only for plain variable names.
+            if let PatternType::Binding(name) = &pattern.pattern_type {
+                self.environment.define_declared(name, None)?;
+            }
+            return Ok(());
         };
-        self.environment.define(name.lexeme.to_string(), value);
-        Ok(())
+        let value = self.evaluate(expr)?;
+        self.destructure(pattern, value, Binding::Variable)
     }
 
-    fn execute_fun_declaration_stmt(&mut self, name: Rc<Token>, stmt: &Stmt, arity: usize) {
-        self.environment.define(
-            name.lexeme.to_string(),
-            Some(Literals::Function(
-                // len() - 1 is the index of function declaration just pushed
-                // in declaration_refs. To be later used when evaluating call expr.
-                Callable::new_foreign_fn(
-                    Rc::new(stmt.clone()),
-                    name.lexeme.to_string(),
-                    arity,
-                    self.environment.scope.clone(),
-                ),
-            )),
+    /// Binds value to every name in pattern. Names are either defined
+    /// in current scope (declaration) or assigned to where they resolve.
+    fn destructure(
+        &mut self,
+        pattern: &Pattern,
+        value: Literals,
+        binding: Binding,
+    ) -> Result<(), RuntimeError> {
+        match &pattern.pattern_type {
+            PatternType::Binding(name) => {
+                match binding {
+                    Binding::Variable => self.environment.define_declared(name, Some(value))?,
+                    Binding::Constant => self.environment.define_constant(name.clone(), value)?,
+                    Binding::Assign => {
+                        self.assign_variable(name.clone(), value, pattern.id)?;
+                    }
+                }
+                Ok(())
+            }
+            PatternType::List(bracket, elements) => {
+                let Literals::List(list) = value else {
+                    return Err(RuntimeError::new(
+                        bracket.clone(),
+                        format!("Can only destructure a list into '{pattern}'"),
+                    ));
+                };
+                let values = list.elements.borrow().clone();
+                self.destructure_elements(pattern, elements, values, binding)
+            }
+            PatternType::Tuple(paren, elements) => {
+                let Literals::Tuple(values) = value else {
+                    return Err(RuntimeError::new(
+                        paren.clone(),
+                        format!("Can only destructure a tuple into '{pattern}'"),
+                    ));
+                };
+                self.destructure_elements(pattern, elements, values.to_vec(), binding)
+            }
+            PatternType::Literal(_, _)
+            | PatternType::Wildcard(_)
+            | PatternType::Alternatives(_)
+            | PatternType::Instance { name: _, class: _, fields: _ }
+            | PatternType::Variant { name: _, enumeration: _, variant: _, payload: _ } => {
+                unreachable!("Refutable patterns are only parsed in match arms")
+            }
+            PatternType::Fields(brace, fields) => {
+                if !matches!(value, Literals::Instance(_) | Literals::Map(_)) {
+                    return Err(RuntimeError::new(
+                        brace.clone(),
+                        format!("Can only destructure fields of an instance or map into '{pattern}'"),
+                    ));
+                }
+                for field in fields {
+                    let name = field.token();
+                    let value = self.read_field(&value, name.clone())?.ok_or_else(|| RuntimeError::new(
+                        name.clone(),
+                        format!("Undefined property '{field}' to destructure into '{pattern}'"),
+                    ))?;
+                    self.destructure(field, value, binding)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn execute_fun_declaration_stmt(
+        &mut self,
+        name: Rc<Token>,
+        stmt: &Stmt,
+        arity: usize,
+        decorators: &[Decorator]
+    ) -> Result<(), RuntimeError> {
+        let decorators = self.evaluate_decorators(decorators)?;
+        let function = Literals::Function(
+            Callable::new_foreign_fn(
+                Rc::new(stmt.clone()),
+                name.lexeme.to_string(),
+                arity,
+                self.environment.scope.clone(),
+                // Functions nested in methods share their access to private members.
+                self.current_class.clone(),
+            ),
         );
+        let function = self.decorate(function, &decorators)?;
+        self.environment.define_declared(&name, Some(function))?;
+        Ok(())
     }
 
     fn execute_return_stmt(
@@ -551,7 +1534,60 @@ impl<'p, T: Print> Interpreter<'p, T> {
     ) -> Result<Literals, RuntimeError> {
         let value = self.evaluate(rvalue)?;
         // self.environment.assign(name, value)
-        if let Some(distance) = self.locals.get(&expr.id) {
+        self.assign_variable(name, value, expr.id)
+    }
+
+    /// Binds values of a list or tuple to element patterns by position.
+    fn destructure_elements(
+        &mut self,
+        pattern: &Pattern,
+        elements: &[Pattern],
+        values: Vec<Literals>,
+        binding: Binding,
+    ) -> Result<(), RuntimeError> {
+        if values.len() > elements.len() {
+            return Err(RuntimeError::new(
+                pattern.token(),
+                format!(
+                    "Too many values to destructure into '{pattern}', expected {} received {}",
+                    elements.len(),
+                    values.len()
+                ),
+            ));
+        }
+        let mut values = values.into_iter();
+        for element in elements {
+            let Some(value) = values.next() else {
+                return Err(RuntimeError::new(
+                    element.token(),
+                    format!("No value to destructure into '{element}' of '{pattern}'"),
+                ));
+            };
+            self.destructure(element, value, binding)?;
+        }
+        Ok(())
+    }
+
+    fn execute_assign_pattern_expr(
+        &mut self,
+        target: &Pattern,
+        rvalue: &Expr,
+    ) -> Result<Literals, RuntimeError> {
+        // Evaluate the whole rvalue before assigning any of the names,
+        // so that `[a, b] = [b, a];` swaps values.
+        let value = self.evaluate(rvalue)?;
+        self.destructure(target, value.clone(), Binding::Assign)?;
+        Ok(value)
+    }
+
+    /// Assigns to variable resolved for expression or pattern with given id.
+    fn assign_variable(
+        &mut self,
+        name: Rc<Token>,
+        value: Literals,
+        id: usize,
+    ) -> Result<Literals, RuntimeError> {
+        if let Some(distance) = self.locals.get(&id) {
             self.environment.assign_at(*distance, name, value)
         } else {
             self.environment.assign_global(name, value)
@@ -585,7 +1621,7 @@ mod test {
             panic!("Error while scanning.");
         }
 
-        let parser = Parser::new(&scanner.tokens, &error_reporter);
+        let parser = Parser::new(&scanner.tokens, &error_reporter, false);
         let Stmt::Expression(ast) = &parser.parse()[0] else {panic!()};
         if error_reporter.had_error.get() {
             panic!("Error while parsing.");
@@ -0,0 +1,81 @@
+use std::{cell::{Cell, RefCell}, rc::Rc};
+
+use super::{expr::Literals, interpreter::RuntimeError, token::Token};
+
+thread_local!{ 
+    pub static LIST_ID: Cell<usize> = const { Cell::new(1) };
+}
+
+#[derive(Debug, Clone)]
+pub struct List {
+    id: usize,
+    pub elements: Rc<RefCell<Vec<Literals>>>,
+    frozen: Rc<Cell<bool>>
+}
+
+impl List {
+    pub fn new(elements: Vec<Literals>) -> Self {
+        Self {
+            id: Self::get_inc_list_id(),
+            elements: Rc::new(RefCell::new(elements)),
+            frozen: Rc::new(Cell::new(false))
+        }
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    fn get_inc_list_id() -> usize {
+        // Assign new incrementing id to every new list.
+        let mut id: usize = 0;
+        LIST_ID.with(|list_id| {
+            id = list_id.get();
+            list_id.set(id + 1);
+        });
+        id
+    }
+
+    pub fn freeze(&self) {
+        self.frozen.set(true);
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen.get()
+    }
+
+    pub fn get(&self, bracket: Rc<Token>, index: &Literals) -> Result<Literals, RuntimeError> {
+        let i = self.index(bracket, index)?;
+        Ok(self.elements.borrow()[i].clone())
+    }
+
+    pub fn set(&self, bracket: Rc<Token>, index: &Literals, value: Literals) -> Result<(), RuntimeError> {
+        if self.is_frozen() {
+            return Err(RuntimeError::new(bracket, "Can't modify frozen list".to_string()));
+        }
+        let i = self.index(bracket, index)?;
+        self.elements.borrow_mut()[i] = value;
+        Ok(())
+    }
+
+    /// Converts index value into position of an existing element.
+    fn index(&self, bracket: Rc<Token>, index: &Literals) -> Result<usize, RuntimeError> {
+        let Literals::Number(n) = index else {
+            return Err(RuntimeError::new(bracket, "List index must be a number".to_string()));
+        };
+        let len = self.elements.borrow().len();
+        if n.fract() != 0.0 || *n < 0.0 || *n >= len as f64 {
+            return Err(RuntimeError::new(
+                bracket,
+                format!("List index {n} out of range for list of length {len}"),
+            ));
+        }
+        Ok(*n as usize)
+    }
+}
+
+impl PartialEq for List {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
@@ -0,0 +1,515 @@
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use super::{
+    error_reporter::ErrorReporter,
+    expr::{Expr, ExprType, Literals},
+    pattern::{Pattern, PatternType},
+    printer::Print,
+    stmt::{MacroDeclaration, Stmt},
+    token::Token,
+};
+
+/// Macros by name, as kept between runs.
+pub type Macros = HashMap<String, Rc<MacroDeclaration>>;
+
+/// Expands macro calls in the program, run after parser and before
+/// resolver. Macros are declared at top level with
+/// `macro name(params) { body }` and called like functions; every call is
+/// replaced with a copy of the macro body, a block expression, where the
+/// parameters are replaced with argument expressions, unevaluated.
+///
+/// Expansion is hygienic: variables declared in the macro body get fresh
+/// names in every expansion, so they don't capture the user's variables
+/// passed in as arguments.
+pub struct MacroExpander<'a, 'p, T: Print> {
+    err_reporter: &'a ErrorReporter<'a, 'p, T>,
+    /// Macros declared by the program being expanded.
+    macros: Macros,
+    /// Macros declared by earlier runs, such as previous REPL lines. They
+    /// may be declared again, and get macros of this program once expanded.
+    earlier: &'a mut Macros,
+    /// Macros whose expansion is being expanded, innermost last.
+    expanding: Vec<Rc<MacroDeclaration>>,
+    /// Count of fresh names given to variables declared in macros.
+    fresh_names: usize,
+}
+
+impl<'a, 'p, T: Print> MacroExpander<'a, 'p, T> {
+    pub fn new(err_reporter: &'a ErrorReporter<'a, 'p, T>, earlier: &'a mut Macros) -> Self {
+        Self {
+            err_reporter,
+            macros: HashMap::new(),
+            earlier,
+            expanding: vec![],
+            fresh_names: 0,
+        }
+    }
+
+    /// Returns program without macro declarations and with every macro
+    /// call expanded. Macros can be called before their declaration.
+    pub fn expand(&mut self, stmts: Vec<Stmt>) -> Vec<Stmt> {
+        let mut program = vec![];
+        for stmt in stmts {
+            match stmt {
+                Stmt::Macro(declaration) => self.declare(declaration),
+                stmt => program.push(stmt),
+            }
+        }
+        for stmt in &mut program {
+            self.stmt(stmt);
+        }
+        self.earlier.extend(self.macros.drain());
+        program
+    }
+
+    fn lookup(&self, name: &str) -> Option<&Rc<MacroDeclaration>> {
+        self.macros.get(name).or_else(|| self.earlier.get(name))
+    }
+
+    fn declare(&mut self, declaration: MacroDeclaration) {
+        if let Some(previous) = self.macros.get(&declaration.name.lexeme) {
+            self.err_reporter.error_token(
+                declaration.name.clone(),
+                &format!("Macro '{}' is already declared", declaration.name.lexeme),
+            );
+            self.err_reporter.note_token(
+                previous.name.clone(),
+                &format!("'{}' is first declared here", declaration.name.lexeme),
+            );
+            return;
+        }
+        self.macros.insert(declaration.name.lexeme.to_string(), Rc::new(declaration));
+    }
+
+    /// Reports error at the macro call along with the macro declaration.
+    fn expansion_error(&self, call: &Rc<Token>, declaration: &MacroDeclaration, message: &str) {
+        self.err_reporter.error_token(call.clone(), message);
+        self.err_reporter.note_token(
+            declaration.name.clone(),
+            &format!("Macro '{}' is declared here", declaration.name.lexeme),
+        );
+    }
+
+    fn expand_call(&mut self, name: &Rc<Token>, declaration: Rc<MacroDeclaration>, arguments: Vec<Expr>) -> Expr {
+        if self.expanding.iter().any(|expanding| Rc::ptr_eq(expanding, &declaration)) {
+            self.expansion_error(name, &declaration, &format!("Macro '{}' expands into itself", name.lexeme));
+            return ExprType::Literal(Literals::Nil).into();
+        }
+        if arguments.len() != declaration.params.len() {
+            self.expansion_error(
+                name,
+                &declaration,
+                &format!(
+                    "Macro '{}' expects {} arguments, received {}",
+                    name.lexeme,
+                    declaration.params.len(),
+                    arguments.len()
+                ),
+            );
+            return ExprType::Literal(Literals::Nil).into();
+        }
+
+        let mut declared = DeclaredNames::default();
+        let mut body = declaration.body.clone();
+        declared.expr(&mut body);
+        let arguments = declaration.params.iter()
+            .map(|param| param.lexeme.to_string())
+            .zip(arguments)
+            .collect();
+        let renames = declared.names.into_iter()
+            .filter(|name| !declaration.params.iter().any(|param| param.lexeme == *name))
+            .map(|name| {
+                self.fresh_names += 1;
+                let fresh = format!("{name}@{}", self.fresh_names);
+                (name, fresh)
+            })
+            .collect();
+        let mut instantiation = Instantiation { arguments, renames, call: name, errors: vec![] };
+        instantiation.expr(&mut body);
+        for (param, message) in instantiation.errors {
+            self.expansion_error(name, &declaration, &message);
+            self.err_reporter.note_token(param, "Argument is used here");
+        }
+
+        // Expansion may call other macros.
+        self.expanding.push(declaration);
+        self.expr(&mut body);
+        self.expanding.pop();
+        body
+    }
+}
+
+impl<'a, 'p, T: Print> Rewrite for MacroExpander<'a, 'p, T> {
+    fn stmt(&mut self, stmt: &mut Stmt) {
+        if let Stmt::Macro(declaration) = stmt {
+            self.err_reporter.error_token(
+                declaration.name.clone(),
+                "Macros can only be declared at top level",
+            );
+            return;
+        }
+        walk_stmt(self, stmt);
+    }
+
+    fn expr(&mut self, expr: &mut Expr) {
+        match &mut expr.expr_type {
+            ExprType::Call { callee, paren: _, arguments } => {
+                let ExprType::Variable(name) = &callee.expr_type else {
+                    return walk_expr(self, expr);
+                };
+                let Some(declaration) = self.lookup(&name.lexeme).cloned() else {
+                    return walk_expr(self, expr);
+                };
+                let name = name.clone();
+                for argument in arguments.iter_mut() {
+                    self.expr(argument);
+                }
+                let arguments = std::mem::take(arguments);
+                *expr = self.expand_call(&name, declaration, arguments);
+            }
+            ExprType::Variable(name) if self.lookup(&name.lexeme).is_some() => {
+                self.err_reporter.error_token(
+                    name.clone(),
+                    &format!("Macro '{}' can only be called", name.lexeme),
+                );
+            }
+            _ => walk_expr(self, expr),
+        }
+    }
+}
+
+/// Collects names of variables, functions and types declared in macro body.
+#[derive(Default)]
+struct DeclaredNames {
+    names: HashSet<String>,
+}
+
+impl Rewrite for DeclaredNames {
+    fn declaration(&mut self, name: &mut Rc<Token>) {
+        self.names.insert(name.lexeme.to_string());
+    }
+}
+
+/// Turns copy of macro body into expansion of a single call.
+struct Instantiation<'c> {
+    /// Argument expressions by parameter names.
+    arguments: HashMap<String, Expr>,
+    /// Fresh names of variables declared in the macro body.
+    renames: HashMap<String, String>,
+    /// Name of the macro at the call.
+    call: &'c Rc<Token>,
+    /// Parameters used in ways their arguments don't allow, with message.
+    errors: Vec<(Rc<Token>, String)>,
+}
+
+impl<'c> Instantiation<'c> {
+    /// Copy of argument with its own expression ids, as argument may be
+    /// used more than once.
+    fn argument(&self, param: &Rc<Token>) -> Option<Expr> {
+        let mut argument = self.arguments.get(&param.lexeme)?.clone();
+        Renumber.expr(&mut argument);
+        Some(argument)
+    }
+}
+
+impl<'c> Rewrite for Instantiation<'c> {
+    fn expr(&mut self, expr: &mut Expr) {
+        expr.id = Expr::get_inc_expr_id();
+        match &mut expr.expr_type {
+            ExprType::Variable(name) => {
+                if let Some(argument) = self.argument(name) {
+                    *expr = argument;
+                } else {
+                    self.name(name);
+                }
+            }
+            ExprType::Assign(name, value) if self.arguments.contains_key(&name.lexeme) => {
+                self.expr(value);
+                let value = value.clone();
+                let argument = self.argument(name).unwrap();
+                expr.expr_type = match argument.expr_type {
+                    ExprType::Variable(target) => ExprType::Assign(target, value),
+                    ExprType::Get { object, property } => ExprType::Set { object, property, value },
+                    ExprType::Index { object, bracket, index } => {
+                        ExprType::SetIndex { object, bracket, index, value }
+                    }
+                    _ => {
+                        self.errors.push((
+                            name.clone(),
+                            format!(
+                                "Can't assign to argument '{}' of macro '{}'",
+                                name.lexeme, self.call.lexeme
+                            ),
+                        ));
+                        return;
+                    }
+                };
+            }
+            _ => walk_expr(self, expr),
+        }
+    }
+
+    fn pattern(&mut self, pattern: &mut Pattern) {
+        pattern.id = Expr::get_inc_expr_id();
+    }
+
+    fn name(&mut self, name: &mut Rc<Token>) {
+        if let Some(fresh) = self.renames.get(&name.lexeme) {
+            *name = Rc::new(Token {
+                token_type: name.token_type.clone(),
+                lexeme: fresh.to_string(),
+                line: name.line,
+                doc: None,
+            });
+        }
+    }
+}
+
+/// Gives every expression and pattern a new id.
+struct Renumber;
+
+impl Rewrite for Renumber {
+    fn expr(&mut self, expr: &mut Expr) {
+        expr.id = Expr::get_inc_expr_id();
+        walk_expr(self, expr);
+    }
+
+    fn pattern(&mut self, pattern: &mut Pattern) {
+        pattern.id = Expr::get_inc_expr_id();
+    }
+}
+
+/// Visits and changes syntax tree in place. Default methods visit all
+/// children of the node.
+trait Rewrite {
+    fn stmt(&mut self, stmt: &mut Stmt) {
+        walk_stmt(self, stmt);
+    }
+
+    fn expr(&mut self, expr: &mut Expr) {
+        walk_expr(self, expr);
+    }
+
+    /// Pattern node, visited before its children.
+    fn pattern(&mut self, _pattern: &mut Pattern) {}
+
+    /// Name of variable read or assigned.
+    fn name(&mut self, _name: &mut Rc<Token>) {}
+
+    /// Name of variable, parameter, function or type being declared.
+    fn declaration(&mut self, name: &mut Rc<Token>) {
+        self.name(name);
+    }
+}
+
+fn walk_stmt<V: Rewrite + ?Sized>(v: &mut V, stmt: &mut Stmt) {
+    match stmt {
+        Stmt::Expression(expr) | Stmt::Print(expr) => v.expr(expr),
+        Stmt::Function { name, params, body, decorators, signature: _ } => {
+            v.declaration(name);
+            for decorator in decorators.iter_mut() {
+                v.expr(&mut decorator.expression);
+            }
+            walk_function(v, params, body);
+        }
+        Stmt::If(condition, then_stmt, else_stmt) => {
+            v.expr(condition);
+            v.stmt(then_stmt);
+            if let Some(else_stmt) = else_stmt.as_mut() {
+                v.stmt(else_stmt);
+            }
+        }
+        Stmt::Var(pattern, _, initializer) => {
+            if let Some(initializer) = initializer {
+                v.expr(initializer);
+            }
+            walk_pattern(v, pattern, true);
+        }
+        Stmt::Const(pattern, _, initializer) => {
+            v.expr(initializer);
+            walk_pattern(v, pattern, true);
+        }
+        Stmt::Block(stmts) => {
+            for stmt in stmts {
+                v.stmt(stmt);
+            }
+        }
+        Stmt::While { label: _, condition, body, do_while: _ } => {
+            v.expr(condition);
+            v.stmt(body);
+        }
+        Stmt::Break { .. } => (),
+        Stmt::Match { keyword: _, subject, arms } => {
+            v.expr(subject);
+            for arm in arms {
+                walk_pattern(v, &mut arm.pattern, true);
+                if let Some(guard) = &mut arm.guard {
+                    v.expr(guard);
+                }
+                v.stmt(&mut arm.body);
+            }
+        }
+        Stmt::Return { return_keyword: _, expression } => {
+            if let Some(expression) = expression {
+                v.expr(expression);
+            }
+        }
+        Stmt::Class(class) => {
+            v.declaration(&mut class.name);
+            for trait_expr in &mut class.traits {
+                v.expr(trait_expr);
+            }
+            for field in &mut class.static_fields {
+                if let Some(initializer) = &mut field.initializer {
+                    v.expr(initializer);
+                }
+            }
+            let methods = class.methods.iter_mut()
+                .chain(&mut class.getters)
+                .chain(&mut class.setters)
+                .chain(&mut class.class_methods);
+            walk_methods(v, methods);
+        }
+        Stmt::Trait { name, methods, required: _ } => {
+            v.declaration(name);
+            walk_methods(v, methods.iter_mut());
+        }
+        Stmt::Extend { name: _, class, methods } => {
+            v.expr(class);
+            walk_methods(v, methods.iter_mut());
+        }
+        Stmt::Enum { name, variants: _ } => v.declaration(name),
+        Stmt::Macro(_) => (),
+    }
+}
+
+/// Methods are named after properties, so only their bodies are visited.
+fn walk_methods<'s, V: Rewrite + ?Sized>(v: &mut V, methods: impl Iterator<Item = &'s mut Stmt>) {
+    for method in methods {
+        if let Stmt::Function { name: _, params, body, decorators, signature: _ } = method {
+            for decorator in decorators.iter_mut() {
+                v.expr(&mut decorator.expression);
+            }
+            walk_function(v, params, body);
+        }
+    }
+}
+
+fn walk_function<V: Rewrite + ?Sized>(v: &mut V, params: &mut [Rc<Token>], body: &mut [Stmt]) {
+    for param in params {
+        v.declaration(param);
+    }
+    for stmt in body {
+        v.stmt(stmt);
+    }
+}
+
+fn walk_expr<V: Rewrite + ?Sized>(v: &mut V, expr: &mut Expr) {
+    match &mut expr.expr_type {
+        ExprType::Assign(name, value) => {
+            v.name(name);
+            v.expr(value);
+        }
+        ExprType::AssignPattern(target, value) => {
+            walk_pattern(v, target, false);
+            v.expr(value);
+        }
+        ExprType::Binary(left, _, right) | ExprType::Logical(left, _, right) => {
+            v.expr(left);
+            v.expr(right);
+        }
+        ExprType::Grouping(inner) | ExprType::Unary(_, inner) => v.expr(inner),
+        ExprType::Literal(_) | ExprType::This(_) => (),
+        ExprType::Variable(name) => v.name(name),
+        ExprType::Call { callee, paren: _, arguments } => {
+            v.expr(callee);
+            for argument in arguments {
+                v.expr(argument);
+            }
+        }
+        ExprType::Get { object, property: _ } => v.expr(object),
+        ExprType::Set { object, property: _, value } => {
+            v.expr(object);
+            v.expr(value);
+        }
+        ExprType::List { bracket: _, elements } | ExprType::Tuple { paren: _, elements } => {
+            for element in elements {
+                v.expr(element);
+            }
+        }
+        ExprType::Map { brace: _, entries } => {
+            for (key, value) in entries {
+                v.expr(key);
+                v.expr(value);
+            }
+        }
+        ExprType::Index { object, bracket: _, index } => {
+            v.expr(object);
+            v.expr(index);
+        }
+        ExprType::SetIndex { object, bracket: _, index, value } => {
+            v.expr(object);
+            v.expr(index);
+            v.expr(value);
+        }
+        ExprType::Block { brace: _, statements, value } => {
+            for stmt in statements {
+                v.stmt(stmt);
+            }
+            if let Some(value) = value {
+                v.expr(value);
+            }
+        }
+        ExprType::If { keyword: _, condition, then_branch, else_branch } => {
+            v.expr(condition);
+            v.expr(then_branch);
+            if let Some(else_branch) = else_branch {
+                v.expr(else_branch);
+            }
+        }
+    }
+}
+
+/// Visits pattern and its children. Bindings are declarations in patterns
+/// of declarations and match arms, and assignment targets otherwise.
+fn walk_pattern<V: Rewrite + ?Sized>(v: &mut V, pattern: &mut Pattern, declared: bool) {
+    v.pattern(pattern);
+    match &mut pattern.pattern_type {
+        PatternType::Binding(name) if declared => v.declaration(name),
+        PatternType::Binding(name) => v.name(name),
+        PatternType::Literal(_, _) | PatternType::Wildcard(_) => (),
+        PatternType::List(_, elements)
+        | PatternType::Tuple(_, elements)
+        | PatternType::Alternatives(elements) => {
+            for element in elements {
+                walk_pattern(v, element, declared);
+            }
+        }
+        PatternType::Fields(_, fields) => walk_fields(v, fields, declared),
+        PatternType::Instance { name: _, class, fields } => {
+            v.expr(class);
+            walk_fields(v, fields, declared);
+        }
+        PatternType::Variant { name: _, enumeration, variant: _, payload } => {
+            v.expr(enumeration);
+            for element in payload.iter_mut().flatten() {
+                walk_pattern(v, element, declared);
+            }
+        }
+    }
+}
+
+/// Field bindings such as `{ x }` are named after the property they read,
+/// so they keep their names.
+fn walk_fields<V: Rewrite + ?Sized>(v: &mut V, fields: &mut [Pattern], declared: bool) {
+    for field in fields {
+        if matches!(field.pattern_type, PatternType::Binding(_)) {
+            v.pattern(field);
+        } else {
+            walk_pattern(v, field, declared);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use std::{cell::{Cell, RefCell}, collections::{hash_map::DefaultHasher, HashMap}, hash::{Hash, Hasher}, rc::Rc};
+
+use super::{expr::Literals, interpreter::RuntimeError, token::Token};
+
+thread_local!{ 
+    pub static MAP_ID: Cell<usize> = const { Cell::new(1) };
+}
+
+/// Map of keys to values, keeping entries in insertion order.
+/// Keys are found by their hash, then compared with Lox equality.
+#[derive(Debug, Clone)]
+pub struct Map {
+    id: usize,
+    pub entries: Rc<RefCell<Vec<(Literals, Literals)>>>,
+    /// Positions in entries of keys with the same hash.
+    index: Rc<RefCell<HashMap<u64, Vec<usize>>>>,
+    frozen: Rc<Cell<bool>>
+}
+
+impl Map {
+    pub fn new(entries: Vec<(Literals, Literals)>) -> Self {
+        let map = Self {
+            id: Self::get_inc_map_id(),
+            entries: Rc::new(RefCell::new(vec![])),
+            index: Rc::new(RefCell::new(HashMap::new())),
+            frozen: Rc::new(Cell::new(false))
+        };
+        for (key, value) in entries {
+            map.insert(key, value);
+        }
+        map
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    fn get_inc_map_id() -> usize {
+        // Assign new incrementing id to every new map.
+        let mut id: usize = 0;
+        MAP_ID.with(|map_id| {
+            id = map_id.get();
+            map_id.set(id + 1);
+        });
+        id
+    }
+
+    pub fn freeze(&self) {
+        self.frozen.set(true);
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen.get()
+    }
+
+    pub fn lookup(&self, key: &Literals) -> Option<Literals> {
+        let position = self.position(key, Self::hash_of(key))?;
+        Some(self.entries.borrow()[position].1.clone())
+    }
+
+    fn position(&self, key: &Literals, hash: u64) -> Option<usize> {
+        let entries = self.entries.borrow();
+        self.index.borrow().get(&hash)?.iter()
+        .copied()
+        .find(|&position| entries[position].0 == *key)
+    }
+
+    fn hash_of(key: &Literals) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    pub fn get(&self, bracket: Rc<Token>, key: &Literals) -> Result<Literals, RuntimeError> {
+        self.lookup(key).ok_or_else(|| RuntimeError::new(
+            bracket,
+            format!("Undefined key {} in map", key.repr())
+        ))
+    }
+
+    pub fn set(&self, bracket: Rc<Token>, key: Literals, value: Literals) -> Result<(), RuntimeError> {
+        if self.is_frozen() {
+            return Err(RuntimeError::new(bracket, "Can't modify frozen map".to_string()));
+        }
+        self.insert(key, value);
+        Ok(())
+    }
+
+    fn insert(&self, key: Literals, value: Literals) {
+        let hash = Self::hash_of(&key);
+        let position = self.position(&key, hash);
+        let mut entries = self.entries.borrow_mut();
+        match position {
+            Some(position) => entries[position].1 = value,
+            None => {
+                self.index.borrow_mut().entry(hash).or_default().push(entries.len());
+                entries.push((key, value));
+            }
+        }
+    }
+}
+
+impl PartialEq for Map {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
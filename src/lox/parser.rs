@@ -2,28 +2,38 @@ use std::{cell::Cell, rc::Rc, vec};
 
 use crate::lox::expr::Literals;
 
-use super::{expr::{Expr, ExprType}, token::Token, token_type::TokenType, error_reporter::ErrorReporter, stmt::Stmt, printer::Print};
+use super::{expr::{Expr, ExprType}, token::Token, token_type::TokenType, error_reporter::ErrorReporter, stmt::{Stmt, MatchArm, EnumVariant, StaticField, ClassDeclaration, RequiredMethod, Decorator, TypeAnnotation, Signature, MacroDeclaration}, printer::Print, pattern::{Pattern, PatternType}};
 
 struct LoxParseError;
 pub struct Parser<'a, 'p, T: Print> {
     tokens: &'a Vec<Rc<Token>>,
     current: Cell<usize>,
-    err_reporter: &'a ErrorReporter<'a, 'p, T>
+    err_reporter: &'a ErrorReporter<'a, 'p, T>,
+    /// Whether scanner ran with optional semicolons, emitting Newline tokens
+    /// and always ending the last line with one.
+    optional_semicolons: bool
 }
 
 impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
 
-    pub fn new(tokens: &'a Vec<Rc<Token>>, err_reporter: &'a ErrorReporter<'a, 'p, T>) -> Self {
+    pub fn new(
+        tokens: &'a Vec<Rc<Token>>, err_reporter: &'a ErrorReporter<'a, 'p, T>, optional_semicolons: bool
+    ) -> Self {
         Self {
             tokens,
             current: Cell::new(0),
-            err_reporter
+            err_reporter,
+            optional_semicolons
         }
     }
 
     pub fn parse(&self) -> Vec<Stmt> {
         let mut statements: Vec<Stmt> = vec![];
         while !self.is_at_end() {
+            // Statement ending with '}' leaves line end after it unused.
+            if self.r#match([TokenType::Newline]) {
+                continue;
+            }
             if let Some(stmt) = self.declaration() {
                 statements.push(stmt)
             }
@@ -35,10 +45,24 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
     fn declaration(&self) -> Option<Stmt> {
         let stmt = if self.r#match([TokenType::Class]) {
             self.class_declaration()
+        } else if self.r#match([TokenType::Enum]) {
+            self.enum_declaration()
+        } else if self.r#match([TokenType::Trait]) {
+            self.trait_declaration()
+        } else if self.check_lexeme("extend") && self.check_next(&TokenType::Identifier) {
+            // "extend" is a keyword only in front of a type name.
+            self.advance();
+            self.extend_declaration()
+        } else if self.r#match([TokenType::Macro]) {
+            self.macro_declaration()
         } else if self.r#match([TokenType::Fun]) {
             self.function_declaration("function")
+        } else if self.check(&TokenType::At) {
+            self.decorated_function_declaration()
         } else if self.r#match([TokenType::Var]) {
             self.var_declaration()
+        } else if self.r#match([TokenType::Const]) {
+            self.const_declaration()
         } else {
             self.statement()
         };
@@ -51,22 +75,211 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
 
     fn class_declaration(&self) -> Result<Stmt, LoxParseError> {
         let name = self.consume(TokenType::Identifier, "Expected class name")?;
+        let mut traits = vec![];
+        // "with" is a keyword only after class name.
+        if self.check_lexeme("with") {
+            self.advance();
+            loop {
+                let trait_name = self.consume(TokenType::Identifier, "Expected trait name after 'with'")?;
+                traits.push(ExprType::Variable(trait_name).into());
+                if !self.r#match([TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
         self.consume(TokenType::LeftBrace, "Expected '{' before class body.")?;
 
         let mut methods = vec![];
-        while !self.check(&TokenType::RightBrace) {
-            // TODO: Better error message when non method declaration stmt is found in class.
-            methods.push(self.function_declaration("method")?);
+        let mut getters = vec![];
+        let mut setters = vec![];
+        let mut class_methods = vec![];
+        let mut static_fields = vec![];
+        while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
+            if self.check(&TokenType::At) {
+                // Only instance methods can be decorated.
+                let decorators = self.decorators()?;
+                if self.check(&TokenType::Class) || self.check(&TokenType::Static) {
+                    self.err_reporter.error_token(self.peek(), "Only methods can be decorated");
+                    return Err(LoxParseError);
+                }
+                if self.check_setter() || self.check_getter() {
+                    // Accessor is still parsed, as if it wasn't decorated.
+                    self.err_reporter.error_token(self.peek(), "Only methods can be decorated");
+                    continue;
+                }
+                if self.check_lexeme("init") {
+                    // Initializer is looked up by name when constructing
+                    // instances, so decorating it would have no effect.
+                    self.err_reporter.error_token(self.peek(), "Initializer can't be decorated");
+                }
+                methods.push(self.decorated_function("method", decorators)?);
+            } else if self.r#match([TokenType::Class]) {
+                class_methods.push(self.function_declaration("method")?);
+            } else if self.r#match([TokenType::Static]) {
+                self.consume(TokenType::Var, "Expected 'var' after 'static'")?;
+                let name = self.member_name("Expected static field name")?;
+                let initializer = if self.r#match([TokenType::Equal]) {
+                    Some(self.expression()?)
+                } else {
+                    None
+                };
+                self.consume(TokenType::SemiColon, "Expected ';' after static field declaration")?;
+                static_fields.push(StaticField { name, initializer });
+            } else if self.check_setter() {
+                self.advance();
+                let setter = self.function_declaration("setter")?;
+                if let Stmt::Function { name, params, .. } = &setter {
+                    if params.len() != 1 {
+                        self.err_reporter.error_token(name.clone(), "Setter must take exactly one parameter");
+                    }
+                }
+                setters.push(setter);
+            } else if self.check_getter() {
+                let name = self.advance();
+                self.consume(TokenType::LeftBrace, "Expected '{' before getter body")?;
+                let body = self.block()?;
+                getters.push(Stmt::Function {
+                    name, params: vec![], body, decorators: vec![], signature: Signature::default()
+                });
+            } else {
+                // TODO: Better error message when non method declaration stmt is found in class.
+                methods.push(self.function_declaration("method")?);
+            }
         }
         
         self.consume(TokenType::RightBrace, "Expected '}' after class body")?;
-        Ok(Stmt::Class { name, methods })
+        Ok(Stmt::Class(ClassDeclaration { name, traits, methods, getters, setters, class_methods, static_fields }))
+    }
+
+    fn trait_declaration(&self) -> Result<Stmt, LoxParseError> {
+        let name = self.consume(TokenType::Identifier, "Expected trait name")?;
+        self.consume(TokenType::LeftBrace, "Expected '{' before trait body")?;
+
+        let mut methods = vec![];
+        let mut required = vec![];
+        while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
+            let name = self.consume(TokenType::Identifier, "Expected method name")?;
+            self.consume(TokenType::LeftParen, "Expected '(' after method name")?;
+            let (params, signature) = self.parameters()?;
+            self.skip_newline_before_brace();
+            if self.match_terminator() {
+                // Method without body is required from classes using the trait.
+                required.push(RequiredMethod { name, params });
+                continue;
+            }
+            self.consume(TokenType::LeftBrace, "Expected '{' or ';' after trait method parameters")?;
+            let body = self.block()?;
+            methods.push(Stmt::Function { name, params, body, decorators: vec![], signature });
+        }
+
+        self.consume(TokenType::RightBrace, "Expected '}' after trait body")?;
+        Ok(Stmt::Trait { name, methods, required })
+    }
+
+    fn extend_declaration(&self) -> Result<Stmt, LoxParseError> {
+        let name = self.consume(TokenType::Identifier, "Expected type name after 'extend'")?;
+        self.consume(TokenType::LeftBrace, "Expected '{' before extension body")?;
+
+        let mut methods = vec![];
+        while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
+            methods.push(self.function_declaration("method")?);
+        }
+
+        self.consume(TokenType::RightBrace, "Expected '}' after extension body")?;
+        let class = ExprType::Variable(name.clone()).into();
+        Ok(Stmt::Extend { name, class, methods })
+    }
+
+    fn enum_declaration(&self) -> Result<Stmt, LoxParseError> {
+        let name = self.consume(TokenType::Identifier, "Expected enum name")?;
+        self.consume(TokenType::LeftBrace, "Expected '{' before enum body")?;
+
+        let mut variants = vec![];
+        while !self.check(&TokenType::RightBrace) {
+            let variant = self.consume(TokenType::Identifier, "Expected variant name")?;
+            let mut fields = None;
+            if self.r#match([TokenType::LeftParen]) {
+                let mut payload = vec![];
+                if !self.check(&TokenType::RightParen) {
+                    loop {
+                        payload.push(self.consume(TokenType::Identifier, "Expected payload field name")?);
+                        if !self.r#match([TokenType::Comma]) {
+                            break;
+                        }
+                    }
+                }
+                self.consume(TokenType::RightParen, "Expected ')' after variant payload")?;
+                fields = Some(payload);
+            }
+            variants.push(EnumVariant { name: variant, fields });
+            if !self.r#match([TokenType::Comma]) {
+                break;
+            }
+        }
+
+        self.consume(TokenType::RightBrace, "Expected '}' after enum body")?;
+        Ok(Stmt::Enum { name, variants })
+    }
+
+    fn macro_declaration(&self) -> Result<Stmt, LoxParseError> {
+        let name = self.consume(TokenType::Identifier, "Expected macro name")?;
+        self.consume(TokenType::LeftParen, "Expected '(' after macro name")?;
+        let mut params = vec![];
+        if !self.check(&TokenType::RightParen) {
+            loop {
+                params.push(self.consume(TokenType::Identifier, "Expected parameter name")?);
+                if !self.r#match([TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+        self.consume(TokenType::RightParen, "Expected ')' after macro parameters")?;
+        let brace = self.consume(TokenType::LeftBrace, "Expected '{' before macro body")?;
+        let body = self.block_expression(brace, None)?;
+        Ok(Stmt::Macro(MacroDeclaration { name, params, body }))
+    }
+
+    fn decorated_function_declaration(&self) -> Result<Stmt, LoxParseError> {
+        let decorators = self.decorators()?;
+        self.consume(TokenType::Fun, "Expected function declaration after decorators")?;
+        self.decorated_function("function", decorators)
+    }
+
+    /// Parses decorators, each '@' followed by expression evaluating to
+    /// the decorator, such as `@memoize` or `@retry(3)`.
+    fn decorators(&self) -> Result<Vec<Decorator>, LoxParseError> {
+        let mut decorators = vec![];
+        while self.r#match([TokenType::At]) {
+            let at = self.previous();
+            let expression = self.call()?;
+            decorators.push(Decorator { at, expression });
+        }
+        Ok(decorators)
     }
 
     fn function_declaration(&self, kind: &str) -> Result<Stmt, LoxParseError> {
-        let name = self.consume(TokenType::Identifier, format!("Expected {kind} name").as_str())?;
+        self.decorated_function(kind, vec![])
+    }
+
+    fn decorated_function(&self, kind: &str, decorators: Vec<Decorator>) -> Result<Stmt, LoxParseError> {
+        let name = if kind == "function" {
+            self.consume(TokenType::Identifier, "Expected function name")?
+        } else {
+            self.member_name(format!("Expected {kind} name").as_str())?
+        };
         self.consume(TokenType::LeftParen, format!("Expected '(' after {kind} name").as_str())?;
+        let (params, signature) = self.parameters()?;
+        self.consume(TokenType::LeftBrace, format!("Expected '{{' before {kind} body").as_str())?;
+        let body = self.block()?;
+        Ok(Stmt::Function { name, params, body, decorators, signature })
+    }
+
+    /// Parses parameter list after '(' up to and including closing ')',
+    /// followed by optional return type. Returns parameter names along with
+    /// annotated types.
+    fn parameters(&self) -> Result<(Vec<Rc<Token>>, Signature), LoxParseError> {
         let mut params = vec![];
+        let mut types = vec![];
         if !self.check(&TokenType::RightParen) {
             loop {
                 if params.len() > 255 {
@@ -74,22 +287,54 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
                     return Err(LoxParseError);
                 }
                 params.push(self.consume(TokenType::Identifier, "Expected parameter name")?);
+                types.push(self.annotation()?);
                 if !self.r#match([TokenType::Comma])  { 
                     break 
                 };
             }
         }
         self.consume(TokenType::RightParen, "Expected ')' after parameters")?;
-        self.consume(TokenType::LeftBrace, format!("Expected '{{' before {kind} body").as_str())?;
-        let body = self.block()?;
-        Ok(Stmt::Function { name, params, body })
+        let return_type = self.return_type()?;
+        Ok((params, Signature { params: types, return_type }))
+    }
+
+    /// Parses optional `: Type` after declared name.
+    fn annotation(&self) -> Result<Option<TypeAnnotation>, LoxParseError> {
+        if self.r#match([TokenType::Colon]) {
+            return Ok(Some(self.type_annotation()?));
+        }
+        Ok(None)
+    }
+
+    /// Parses optional `-> Type` after parameter list.
+    fn return_type(&self) -> Result<Option<TypeAnnotation>, LoxParseError> {
+        if self.r#match([TokenType::Arrow]) {
+            return Ok(Some(self.type_annotation()?));
+        }
+        Ok(None)
+    }
+
+    /// Parses type such as `Number`, `List<Number>` or `Map<String, Point?>`.
+    fn type_annotation(&self) -> Result<TypeAnnotation, LoxParseError> {
+        let name = self.consume(TokenType::Identifier, "Expected type name")?;
+        let mut arguments = vec![];
+        if self.r#match([TokenType::Less]) {
+            loop {
+                arguments.push(self.type_annotation()?);
+                if !self.r#match([TokenType::Comma]) {
+                    break;
+                }
+            }
+            self.consume(TokenType::Greater, "Expected '>' after type arguments")?;
+        }
+        let optional = self.r#match([TokenType::Question]);
+        Ok(TypeAnnotation { name, arguments, optional })
     }
 
 
     fn var_declaration(&self) -> Result<Stmt, LoxParseError> {
-        let name = self.consume(
-            TokenType::Identifier, "Expected variable name."
-        )?;
+        let pattern = self.binding_pattern()?;
+        let annotation = self.annotation()?;
         let mut initilizer= None;
         if self.r#match([TokenType::Equal]) {
             // match self.expression() {
@@ -97,22 +342,102 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
             //     Err(e) => return Err(e),
             // };
             initilizer = Some(self.expression()?)
+        } else if !matches!(pattern.pattern_type, PatternType::Binding(_)) {
+            self.err_reporter.error_token(self.previous(), "Expected initializer for destructuring declaration");
+            return Err(LoxParseError);
         }
         self.consume(
             TokenType::SemiColon, "Expected ';' after variable declaration"
         )?;
         // Err(LoxParseError)
-        Ok(Stmt::Var(name, initilizer))
+        Ok(Stmt::Var(pattern, annotation, initilizer))
 
     }
 
+    fn const_declaration(&self) -> Result<Stmt, LoxParseError> {
+        let pattern = self.binding_pattern()?;
+        let annotation = self.annotation()?;
+        self.consume(TokenType::Equal, "Expected initializer for constant")?;
+        let initializer = self.expression()?;
+        self.consume(
+            TokenType::SemiColon, "Expected ';' after constant declaration"
+        )?;
+        Ok(Stmt::Const(pattern, annotation, initializer))
+    }
+
+    /// Parses declaration target: either a plain variable name, a list
+    /// pattern `[a, [b, c]]`, a tuple pattern `(a, b)` or a field pattern `{x, y}`.
+    fn binding_pattern(&self) -> Result<Pattern, LoxParseError> {
+        if self.r#match([TokenType::LeftBracket]) {
+            let bracket = self.previous();
+            let mut elements = vec![];
+            if !self.check(&TokenType::RightBracket) {
+                loop {
+                    elements.push(self.binding_pattern()?);
+                    if !self.r#match([TokenType::Comma]) {
+                        break;
+                    }
+                }
+            }
+            self.consume(TokenType::RightBracket, "Expected ']' after list pattern")?;
+            return Ok(PatternType::List(bracket, elements).into());
+        }
+        if self.r#match([TokenType::LeftParen]) {
+            let paren = self.previous();
+            if self.r#match([TokenType::RightParen]) {
+                // Reported without stopping the parser, as rest of declaration is fine.
+                self.err_reporter.error_token(self.previous(), "Expected pattern in '()', as there are no empty tuples");
+                return Ok(PatternType::Tuple(paren, vec![]).into());
+            }
+            let pattern = self.binding_pattern()?;
+            if !self.r#match([TokenType::Comma]) {
+                // Grouping: (a) binds like a, as (a,) is the single element tuple
+                self.consume(TokenType::RightParen, "Expected ')' after pattern")?;
+                return Ok(pattern);
+            }
+            let mut elements = vec![pattern];
+            while !self.check(&TokenType::RightParen) {
+                elements.push(self.binding_pattern()?);
+                if !self.r#match([TokenType::Comma]) {
+                    break;
+                }
+            }
+            self.consume(TokenType::RightParen, "Expected ')' after tuple pattern")?;
+            return Ok(PatternType::Tuple(paren, elements).into());
+        }
+        if self.r#match([TokenType::LeftBrace]) {
+            let brace = self.previous();
+            let fields = self.field_patterns()?;
+            return Ok(PatternType::Fields(brace, fields).into());
+        }
+        let name = self.consume(
+            TokenType::Identifier, "Expected variable name."
+        )?;
+        Ok(PatternType::Binding(name).into())
+    }
+
     fn statement(&self) -> Result<Stmt, LoxParseError> {
+        if self.check(&TokenType::Identifier) && self.check_next(&TokenType::Colon) {
+            return self.labeled_statement();
+        }
+        if self.r#match([TokenType::Break]) {
+            return self.break_statement();
+        }
+        if self.r#match([TokenType::Do]) {
+            return self.do_while_statement(None);
+        }
         if self.r#match([TokenType::For]) {
-            return self.for_statement();
+            return self.for_statement(None);
+        }
+        if self.r#match([TokenType::Loop]) {
+            return self.loop_statement(None);
         }
         if self.r#match([TokenType::If]) {
             return self.if_statement();
         }
+        if self.r#match([TokenType::Match]) {
+            return self.match_statement();
+        }
         if self.r#match([TokenType::Print]) {
             return self.print_statement();
         }
@@ -120,7 +445,7 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
             return self.return_statement();
         }
         if self.r#match([TokenType::While]) {
-            return self.while_statement();
+            return self.while_statement(None);
         }
         if self.r#match([TokenType::LeftBrace]) {
             return Ok(Stmt::Block(self.block()?));
@@ -128,7 +453,51 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
         self.expression_statement()
     }
 
-    fn for_statement(&self) -> Result<Stmt, LoxParseError> {
+    /// Body of if, else or loop statement. With optional semicolons it may
+    /// start on the line after the condition.
+    fn body(&self) -> Result<Stmt, LoxParseError> {
+        self.r#match([TokenType::Newline]);
+        self.statement()
+    }
+
+    /// Loop named by label, as in `outer: while (...) {}`, for `break outer;`.
+    fn labeled_statement(&self) -> Result<Stmt, LoxParseError> {
+        let label = self.advance();
+        self.advance();
+        if self.r#match([TokenType::Do]) {
+            return self.do_while_statement(Some(label));
+        }
+        if self.r#match([TokenType::For]) {
+            return self.for_statement(Some(label));
+        }
+        if self.r#match([TokenType::Loop]) {
+            return self.loop_statement(Some(label));
+        }
+        if self.r#match([TokenType::While]) {
+            return self.while_statement(Some(label));
+        }
+        self.err_reporter.error_token(label.clone(), &format!("Expected loop after label '{}'", label.lexeme));
+        Err(LoxParseError)
+    }
+
+    fn break_statement(&self) -> Result<Stmt, LoxParseError> {
+        let stmt = self.break_label();
+        self.consume(TokenType::SemiColon, "Expected ';' after 'break'")?;
+        Ok(stmt)
+    }
+
+    /// Parses optional label after 'break'.
+    fn break_label(&self) -> Stmt {
+        let keyword = self.previous();
+        let label = if self.r#match([TokenType::Identifier]) {
+            Some(self.previous())
+        } else {
+            None
+        };
+        Stmt::Break { keyword, label }
+    }
+
+    fn for_statement(&self, label: Option<Rc<Token>>) -> Result<Stmt, LoxParseError> {
         self.consume(TokenType::LeftParen, "Expected '(' after 'for'")?;
 
         let initializer;
@@ -152,7 +521,7 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
         }
         self.consume(TokenType::RightParen, "Expected ')' after for clause")?;
 
-        let mut body = self.statement()?;
+        let mut body = self.body()?;
 
         // 
         // Desugar into While statement
@@ -165,7 +534,7 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
         }
 
         let condition = condition.unwrap_or(Literals::Bool(true).into());
-        body = Stmt::While(condition, Box::new(body));
+        body = Stmt::While { label, condition, body: Box::new(body), do_while: false };
 
         if let Some(initializer) = initializer {
             body = Stmt::Block(vec![
@@ -181,14 +550,186 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
         self.consume(TokenType::LeftParen, "Expected '(' after If")?;
         let condtion = self.expression()?;
         self.consume(TokenType::RightParen, "Expected ')' after If")?;
-        let then_stmt = self.statement()?;
+        let then_stmt = self.body()?;
         let mut else_statement = Box::new(None);
         if self.r#match([TokenType::Else]) {
-            else_statement = Box::new(Some(self.statement()?));
+            else_statement = Box::new(Some(self.body()?));
         }
         Ok(Stmt::If(condtion, Box::new(then_stmt), else_statement))
     }
 
+    fn match_statement(&self) -> Result<Stmt, LoxParseError> {
+        let keyword = self.previous();
+        self.consume(TokenType::LeftParen, "Expected '(' after 'match'")?;
+        let subject = self.expression()?;
+        self.consume(TokenType::RightParen, "Expected ')' after match value")?;
+        self.consume(TokenType::LeftBrace, "Expected '{' before match arms")?;
+
+        let mut arms = vec![];
+        while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
+            let pattern = self.match_pattern()?;
+            let mut guard = None;
+            if self.r#match([TokenType::If]) {
+                guard = Some(self.expression()?);
+            }
+            self.consume(TokenType::FatArrow, "Expected '=>' after match pattern")?;
+            let body = self.match_arm_body()?;
+            arms.push(MatchArm { pattern, guard, body });
+        }
+        self.consume(TokenType::RightBrace, "Expected '}' after match arms")?;
+        Ok(Stmt::Match { keyword, subject, arms })
+    }
+
+    /// Parses body of a match arm: either a block, optionally followed by ',',
+    /// or a single print, return, break or expression statement ended by ',' or ';'.
+    fn match_arm_body(&self) -> Result<Stmt, LoxParseError> {
+        if self.r#match([TokenType::LeftBrace]) {
+            let body = Stmt::Block(self.block()?);
+            self.r#match([TokenType::Comma]);
+            return Ok(body);
+        }
+        let body = if self.r#match([TokenType::Print]) {
+            Stmt::Print(self.expression()?)
+        } else if self.r#match([TokenType::Return]) {
+            let return_keyword = self.previous();
+            let mut expression = None;
+            if ![TokenType::Comma, TokenType::SemiColon, TokenType::Newline, TokenType::RightBrace].iter().any(|t| self.check(t)) {
+                expression = Some(self.expression()?);
+            }
+            Stmt::Return { return_keyword, expression }
+        } else if self.r#match([TokenType::Break]) {
+            self.break_label()
+        } else {
+            Stmt::Expression(self.expression()?)
+        };
+        // Last arm can go without separator before the closing brace.
+        if !self.r#match([TokenType::Comma, TokenType::SemiColon, TokenType::Newline]) && !self.check(&TokenType::RightBrace) {
+            self.err_reporter.error_token(self.previous(), "Expected ',' after match arm");
+            return Err(LoxParseError);
+        }
+        Ok(body)
+    }
+
+    /// Parses pattern of a match arm, which can be made of alternatives `1 | 2`.
+    fn match_pattern(&self) -> Result<Pattern, LoxParseError> {
+        let first = self.single_match_pattern()?;
+        if !self.check(&TokenType::Pipe) {
+            return Ok(first);
+        }
+        let mut alternatives = vec![first];
+        while self.r#match([TokenType::Pipe]) {
+            alternatives.push(self.single_match_pattern()?);
+        }
+        Ok(PatternType::Alternatives(alternatives).into())
+    }
+
+    fn single_match_pattern(&self) -> Result<Pattern, LoxParseError> {
+        if self.r#match([TokenType::False]) {
+            return Ok(PatternType::Literal(self.previous(), Literals::Bool(false)).into());
+        }
+        if self.r#match([TokenType::True]) {
+            return Ok(PatternType::Literal(self.previous(), Literals::Bool(true)).into());
+        }
+        if self.r#match([TokenType::Nil]) {
+            return Ok(PatternType::Literal(self.previous(), Literals::Nil).into());
+        }
+        if self.r#match([TokenType::Minus]) {
+            let minus = self.previous();
+            let number = self.consume(TokenType::Number(0.), "Expected number after '-' in pattern")?;
+            let TokenType::Number(n) = number.token_type else {unreachable!()};
+            return Ok(PatternType::Literal(minus, Literals::Number(-n)).into());
+        }
+        if self.r#match([TokenType::Number(0.)]) {
+            let TokenType::Number(n) = self.previous().token_type else {unreachable!()};
+            return Ok(PatternType::Literal(self.previous(), Literals::Number(n)).into());
+        }
+        if self.r#match([TokenType::String("".to_string())]) {
+            let TokenType::String(s) = self.previous().token_type.clone() else {unreachable!()};
+            return Ok(PatternType::Literal(self.previous(), Literals::String(s)).into());
+        }
+        if self.r#match([TokenType::LeftBracket]) {
+            let bracket = self.previous();
+            let elements = self.match_pattern_elements(TokenType::RightBracket, "Expected ']' after list pattern")?;
+            return Ok(PatternType::List(bracket, elements).into());
+        }
+        if self.r#match([TokenType::LeftParen]) {
+            let paren = self.previous();
+            if self.r#match([TokenType::RightParen]) {
+                // Reported without stopping the parser, as rest of match is fine.
+                self.err_reporter.error_token(self.previous(), "Expected pattern in '()', as there are no empty tuples");
+                return Ok(PatternType::Tuple(paren, vec![]).into());
+            }
+            let pattern = self.match_pattern()?;
+            if !self.r#match([TokenType::Comma]) {
+                // Grouping: (a) matches like a, as (a,) is the single element tuple
+                self.consume(TokenType::RightParen, "Expected ')' after pattern")?;
+                return Ok(pattern);
+            }
+            let mut elements = vec![pattern];
+            while !self.check(&TokenType::RightParen) {
+                elements.push(self.match_pattern()?);
+                if !self.r#match([TokenType::Comma]) {
+                    break;
+                }
+            }
+            self.consume(TokenType::RightParen, "Expected ')' after tuple pattern")?;
+            return Ok(PatternType::Tuple(paren, elements).into());
+        }
+        if self.r#match([TokenType::LeftBrace]) {
+            let brace = self.previous();
+            let fields = self.field_patterns()?;
+            return Ok(PatternType::Fields(brace, fields).into());
+        }
+
+        let name = self.consume(TokenType::Identifier, "Expected pattern")?;
+        if name.lexeme == "_" {
+            return Ok(PatternType::Wildcard(name).into());
+        }
+        if self.r#match([TokenType::LeftBrace]) {
+            let class = Box::new(ExprType::Variable(name.clone()).into());
+            let fields = self.field_patterns()?;
+            return Ok(PatternType::Instance { name, class, fields }.into());
+        }
+        if self.r#match([TokenType::Dot]) {
+            let enumeration = Box::new(ExprType::Variable(name.clone()).into());
+            let variant = self.consume(TokenType::Identifier, "Expected variant name after '.'")?;
+            let mut payload = None;
+            if self.r#match([TokenType::LeftParen]) {
+                payload = Some(self.match_pattern_elements(TokenType::RightParen, "Expected ')' after variant payload pattern")?);
+            }
+            return Ok(PatternType::Variant { name, enumeration, variant, payload }.into());
+        }
+        Ok(PatternType::Binding(name).into())
+    }
+
+    fn match_pattern_elements(&self, closing: TokenType, mssg: &str) -> Result<Vec<Pattern>, LoxParseError> {
+        let mut elements = vec![];
+        if !self.check(&closing) {
+            loop {
+                elements.push(self.match_pattern()?);
+                if !self.r#match([TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+        self.consume(closing, mssg)?;
+        Ok(elements)
+    }
+
+    /// Parses field names of `{x, y}` after the opening brace.
+    fn field_patterns(&self) -> Result<Vec<Pattern>, LoxParseError> {
+        let mut fields = vec![];
+        loop {
+            let name = self.consume(TokenType::Identifier, "Expected field name in pattern")?;
+            fields.push(PatternType::Binding(name).into());
+            if !self.r#match([TokenType::Comma]) {
+                break;
+            }
+        }
+        self.consume(TokenType::RightBrace, "Expected '}' after field pattern")?;
+        Ok(fields)
+    }
+
     fn print_statement(&self) -> Result<Stmt, LoxParseError> {
         let expr = self.expression()?;
         self.consume(TokenType::SemiColon, "Expect ';' after value")?;
@@ -198,19 +739,39 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
     fn return_statement(&self) -> Result<Stmt, LoxParseError> {
         let keyword = self.previous();
         let mut expr = None;
-        if !self.check(&TokenType::SemiColon) {
+        let ends_here = self.check(&TokenType::SemiColon)
+            || self.check(&TokenType::Newline)
+            || self.optional_semicolons && self.check(&TokenType::RightBrace);
+        if !ends_here {
             expr = Some(self.expression()?);
         }
         self.consume(TokenType::SemiColon, "Expect ';' after value")?;
         Ok(Stmt::Return { return_keyword: keyword, expression: expr })
     }
 
-    fn while_statement(&self) -> Result<Stmt, LoxParseError> {
+    fn while_statement(&self, label: Option<Rc<Token>>) -> Result<Stmt, LoxParseError> {
         self.consume(TokenType::LeftParen, "Expected '(' after While")?;
         let condition = self.expression()?;
         self.consume(TokenType::RightParen, "Expected ')' after While")?;
-        let body = self.statement()?;
-        Ok(Stmt::While(condition, Box::new(body)))
+        let body = self.body()?;
+        Ok(Stmt::While { label, condition, body: Box::new(body), do_while: false })
+    }
+
+    fn do_while_statement(&self, label: Option<Rc<Token>>) -> Result<Stmt, LoxParseError> {
+        let body = self.body()?;
+        self.consume(TokenType::While, "Expected 'while' after do-while body")?;
+        self.consume(TokenType::LeftParen, "Expected '(' after While")?;
+        let condition = self.expression()?;
+        self.consume(TokenType::RightParen, "Expected ')' after While")?;
+        self.consume(TokenType::SemiColon, "Expected ';' after do-while condition")?;
+        Ok(Stmt::While { label, condition, body: Box::new(body), do_while: true })
+    }
+
+    /// `loop body` runs forever, until broken out of. Desugars into While
+    /// statement with `true` condition.
+    fn loop_statement(&self, label: Option<Rc<Token>>) -> Result<Stmt, LoxParseError> {
+        let body = self.body()?;
+        Ok(Stmt::While { label, condition: Literals::Bool(true).into(), body: Box::new(body), do_while: false })
     }
 
     fn block(&self) -> Result<Vec<Stmt>, LoxParseError> {
@@ -224,6 +785,69 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
         Ok(statements)
     }
 
+    /// Whether current token starts a declaration or a statement other
+    /// than expression statement.
+    fn starts_statement(&self) -> bool {
+        [
+            TokenType::Class, TokenType::Enum, TokenType::Trait, TokenType::At, TokenType::Fun,
+            TokenType::Var, TokenType::Const, TokenType::For, TokenType::If, TokenType::Match,
+            TokenType::Print, TokenType::Return, TokenType::While, TokenType::LeftBrace,
+            TokenType::Break, TokenType::Do, TokenType::Loop, TokenType::Macro,
+        ].iter().any(|tkn_type| self.check(tkn_type))
+            || self.check_loop_label()
+            || (self.check_lexeme("extend") && self.check_next(&TokenType::Identifier))
+    }
+
+    /// Parses rest of block expression after '{', first is its already
+    /// parsed leading expression, if any.
+    fn block_expression(&self, brace: Rc<Token>, first: Option<Expr>) -> Result<Expr, LoxParseError> {
+        let mut statements = vec![];
+        let mut value = None;
+        let mut next = first;
+        loop {
+            let expr = match next.take() {
+                Some(expr) => expr,
+                None if self.check(&TokenType::RightBrace) || self.is_at_end() => break,
+                None if self.starts_statement() => {
+                    if let Some(stmt) = self.declaration() {
+                        statements.push(stmt);
+                    }
+                    continue;
+                }
+                None => self.expression()?,
+            };
+            // Expression without ';' before '}' is value of the block.
+            if self.check(&TokenType::RightBrace) {
+                value = Some(Box::new(expr));
+                break;
+            }
+            self.consume(TokenType::SemiColon, "Expect ';' after value")?;
+            statements.push(Stmt::Expression(expr));
+        }
+        self.consume(TokenType::RightBrace, "Expected '}' after block")?;
+        Ok(ExprType::Block { brace, statements, value }.into())
+    }
+
+    fn if_expression(&self) -> Result<Expr, LoxParseError> {
+        let keyword = self.previous();
+        self.consume(TokenType::LeftParen, "Expected '(' after 'if'")?;
+        let condition = self.expression()?;
+        self.consume(TokenType::RightParen, "Expected ')' after if condition")?;
+        let then_brace = self.consume(TokenType::LeftBrace, "Expected '{' before branch of if expression")?;
+        let then_branch = self.block_expression(then_brace, None)?;
+        let else_branch = if self.r#match([TokenType::Else]) {
+            if self.r#match([TokenType::If]) {
+                Some(Box::new(self.if_expression()?))
+            } else {
+                let else_brace = self.consume(TokenType::LeftBrace, "Expected '{' or 'if' after 'else'")?;
+                Some(Box::new(self.block_expression(else_brace, None)?))
+            }
+        } else {
+            None
+        };
+        Ok(ExprType::If { keyword, condition: Box::new(condition), then_branch: Box::new(then_branch), else_branch }.into())
+    }
+
     fn expression_statement(&self) -> Result<Stmt, LoxParseError> {
         let expr = self.expression()?;
         self.consume(TokenType::SemiColon, "Expect ';' after value")?;
@@ -235,14 +859,18 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
     }
 
     fn assignment(&self) -> Result<Expr, LoxParseError> {
-        let expr = self.or()?;
+        let expr = self.pipe()?;
         if self.r#match([TokenType::Equal]) {
             let equals = self.previous();
-            let value = self.or()?;
+            let value = self.pipe()?;
             if let ExprType::Variable(var_name) = expr.expr_type {
                 return Ok(ExprType::Assign(var_name, Box::new(value)).into());
             } else if let ExprType::Get { object, property } = expr.expr_type {
                 return Ok(ExprType::Set { object, property, value: Box::new(value) }.into())
+            } else if let ExprType::Index { object, bracket, index } = expr.expr_type {
+                return Ok(ExprType::SetIndex { object, bracket, index, value: Box::new(value) }.into())
+            } else if let Some(target) = Self::assignment_pattern(&expr) {
+                return Ok(ExprType::AssignPattern(target, Box::new(value)).into());
             } else {
                 self.err_reporter.error_token(equals, "Invalid assignment target")
             }
@@ -250,6 +878,44 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
         Ok(expr)
     }
 
+    /// Converts list literal on the left side of '=' into a pattern of
+    /// variables to assign to. Returns None if expr is not a valid target.
+    fn assignment_pattern(expr: &Expr) -> Option<Pattern> {
+        match &expr.expr_type {
+            ExprType::Variable(name) => Some(PatternType::Binding(name.clone()).into()),
+            ExprType::Grouping(inner) => Self::assignment_pattern(inner),
+            ExprType::List { bracket, elements } => {
+                let elements = elements.iter()
+                    .map(Self::assignment_pattern)
+                    .collect::<Option<Vec<_>>>()?;
+                Some(PatternType::List(bracket.clone(), elements).into())
+            }
+            ExprType::Tuple { paren, elements } => {
+                let elements = elements.iter()
+                    .map(Self::assignment_pattern)
+                    .collect::<Option<Vec<_>>>()?;
+                Some(PatternType::Tuple(paren.clone(), elements).into())
+            }
+            _ => None
+        }
+    }
+
+    /// `value |> f(args)` is desugared into call `f(value, args)`.
+    fn pipe(&self) -> Result<Expr, LoxParseError> {
+        let mut expr = self.or()?;
+        while self.r#match([TokenType::PipeGreater]) {
+            let pipe = self.previous();
+            let right = self.or()?;
+            let ExprType::Call { callee, paren, mut arguments } = right.expr_type else {
+                self.err_reporter.error_token(pipe, "Expected function call after '|>', as in 'value |> f()'");
+                return Err(LoxParseError);
+            };
+            arguments.insert(0, expr);
+            expr = ExprType::Call { callee, paren, arguments }.into();
+        }
+        Ok(expr)
+    }
+
     fn or(&self) -> Result<Expr, LoxParseError> {
         let left = self.and()?;
         if self.r#match([TokenType::Or]) {
@@ -287,7 +953,10 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
 
     fn comparison(&self) -> Result<Expr, LoxParseError> {
         let mut expr = self.term()?;
-        while self.r#match([TokenType::Greater, TokenType::GreaterEqual, TokenType::Less, TokenType::LessEqual]) {
+        while self.r#match([
+            TokenType::Greater, TokenType::GreaterEqual, TokenType::Less, TokenType::LessEqual,
+            TokenType::Is, TokenType::In
+        ]) {
             let op = self.previous();
             let right = self.term()?;
             expr = ExprType::Binary(
@@ -328,7 +997,7 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
     }
 
     fn unary(&self) -> Result<Expr, LoxParseError> {
-        if self.r#match([TokenType::Bang, TokenType::Minus]) {
+        if self.r#match([TokenType::Bang, TokenType::Minus, TokenType::Typeof]) {
             let op = self.previous();
             let right = self.unary()?;
             return Ok(ExprType::Unary(op, Box::new(right)).into());
@@ -342,8 +1011,13 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
             if self.r#match([TokenType::LeftParen]) {
                 expr = self.finish_call(expr)?;
             } else if self.r#match([TokenType::Dot]) {
-                let property = self.consume(TokenType::Identifier, "Expected property name after '.'")?;
+                let property = self.member_name("Expected property name after '.'")?;
                 expr = (ExprType::Get { object: Box::new(expr), property }).into();
+            } else if self.r#match([TokenType::LeftBracket]) {
+                let bracket = self.previous();
+                let index = self.expression()?;
+                self.consume(TokenType::RightBracket, "Expected ']' after index")?;
+                expr = (ExprType::Index { object: Box::new(expr), bracket, index: Box::new(index) }).into();
             } else {
                 break;
             }
@@ -391,15 +1065,79 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
             return Ok(ExprType::Literal(Literals::String(s)).into());
         }
 
+        if self.r#match([TokenType::This]) {
+            return Ok(ExprType::This(self.previous()).into())
+        }
+
         if self.r#match([TokenType::Identifier]) {
             return Ok(ExprType::Variable(self.previous()).into())
         }
 
         if self.r#match([TokenType::LeftParen]) {
+            let paren = self.previous();
             let expr = self.expression()?;
+            if self.r#match([TokenType::Comma]) {
+                // Tuple: (a, b) or single element tuple (a,)
+                let mut elements = vec![expr];
+                while !self.check(&TokenType::RightParen) {
+                    elements.push(self.expression()?);
+                    if !self.r#match([TokenType::Comma]) {
+                        break;
+                    }
+                }
+                self.consume(TokenType::RightParen, "Expected ')' after tuple elements")?;
+                return Ok(ExprType::Tuple { paren, elements }.into());
+            }
             self.consume(TokenType::RightParen, "Expect ')' after expression")?;
             return Ok(ExprType::Grouping(Box::new(expr)).into());
         }
+
+        if self.r#match([TokenType::LeftBracket]) {
+            let bracket = self.previous();
+            let mut elements = vec![];
+            if !self.check(&TokenType::RightBracket) {
+                loop {
+                    elements.push(self.expression()?);
+                    if !self.r#match([TokenType::Comma]) {
+                        break;
+                    }
+                }
+            }
+            self.consume(TokenType::RightBracket, "Expected ']' after list elements")?;
+            return Ok(ExprType::List { bracket, elements }.into());
+        }
+
+        if self.r#match([TokenType::If]) {
+            return self.if_expression();
+        }
+
+        if self.r#match([TokenType::LeftBrace]) {
+            let brace = self.previous();
+            // '{}' is an empty map. Otherwise ':' after first expression
+            // tells map from block expression.
+            if self.r#match([TokenType::RightBrace]) {
+                return Ok(ExprType::Map { brace, entries: vec![] }.into());
+            }
+            if self.starts_statement() {
+                return self.block_expression(brace, None);
+            }
+            let first = self.expression()?;
+            if !self.check(&TokenType::Colon) {
+                return self.block_expression(brace, Some(first));
+            }
+            let mut entries = vec![];
+            let mut key = first;
+            loop {
+                self.consume(TokenType::Colon, "Expected ':' after map key")?;
+                entries.push((key, self.expression()?));
+                if !self.r#match([TokenType::Comma]) {
+                    break;
+                }
+                key = self.expression()?;
+            }
+            self.consume(TokenType::RightBrace, "Expected '}' after map entries")?;
+            return Ok(ExprType::Map { brace, entries }.into());
+        }
         // unimplemented!("Incorerect syntax or currently not implemented")
         self.err_reporter.error_token(self.previous(), "Expected Expression");
         Err(LoxParseError)
@@ -416,9 +1154,17 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
     fn consume(
         &self, tkn_type: TokenType, mssg: &str
     ) -> Result<Rc<Token>, LoxParseError>  {
+        if tkn_type == TokenType::LeftBrace {
+            self.skip_newline_before_brace();
+        }
         if self.check(&tkn_type) {
             return Ok(self.advance());
         }
+        if tkn_type == TokenType::SemiColon
+            && (self.match_terminator() || self.optional_semicolons && self.previous().token_type == TokenType::RightBrace)
+        {
+            return Ok(self.previous());
+        }
         self.err_reporter.error_token(self.previous(), mssg);
         Err(LoxParseError)
     }
@@ -430,6 +1176,70 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
         std::mem::discriminant(&self.peek().token_type) == std::mem::discriminant(tkn_type)
     }
 
+    /// Consumes name of class member or property, which may be private.
+    fn member_name(&self, mssg: &str) -> Result<Rc<Token>, LoxParseError> {
+        if self.r#match([TokenType::PrivateIdentifier]) {
+            return Ok(self.previous());
+        }
+        self.consume(TokenType::Identifier, mssg)
+    }
+
+    /// Checks for setter declaration. "set" is a keyword only in front of
+    /// a method name.
+    fn check_setter(&self) -> bool {
+        self.check_lexeme("set")
+            && (self.check_next(&TokenType::Identifier) || self.check_next(&TokenType::PrivateIdentifier))
+    }
+
+    /// Checks for getter declaration, a method declared without parameter
+    /// list. Name ending a line can only be a getter with body on next line.
+    fn check_getter(&self) -> bool {
+        (self.check(&TokenType::Identifier) || self.check(&TokenType::PrivateIdentifier))
+            && (self.check_next(&TokenType::LeftBrace) || self.check_next(&TokenType::Newline))
+    }
+
+    fn check_lexeme(&self, lexeme: &str) -> bool {
+        self.check(&TokenType::Identifier) && self.peek().lexeme == lexeme
+    }
+
+    /// Matches ';' ending a statement. With optional semicolons, line end,
+    /// closing brace or 'else' after the statement also ends it.
+    fn match_terminator(&self) -> bool {
+        self.r#match([TokenType::SemiColon])
+            || self.optional_semicolons
+                && (self.r#match([TokenType::Newline])
+                    || self.check(&TokenType::RightBrace)
+                    || self.check(&TokenType::Else))
+    }
+
+    /// Skips line end before '{', so that opening brace of a body can
+    /// start on the next line.
+    fn skip_newline_before_brace(&self) {
+        if self.check(&TokenType::Newline) && self.check_next(&TokenType::LeftBrace) {
+            self.advance();
+        }
+    }
+
+    /// Checks type of the token after the current one.
+    fn check_next(&self, tkn_type: &TokenType) -> bool {
+        if self.is_at_end() {
+            return false;
+        }
+        let next = &self.tokens[self.current.get() + 1];
+        std::mem::discriminant(&next.token_type) == std::mem::discriminant(tkn_type)
+    }
+
+    /// Checks for label of a loop, as in `outer: while`, which unlike map
+    /// key is followed by loop keyword.
+    fn check_loop_label(&self) -> bool {
+        self.check(&TokenType::Identifier)
+            && self.check_next(&TokenType::Colon)
+            && self.tokens.get(self.current.get() + 2).is_some_and(|token| matches!(
+                token.token_type,
+                TokenType::Do | TokenType::For | TokenType::Loop | TokenType::While
+            ))
+    }
+
     fn advance(&self) -> Rc<Token> {
         if !self.is_at_end() {
             self.current.set(self.current.get() + 1);
@@ -453,12 +1263,12 @@ impl<'a, 'p, T: Print> Parser<'a, 'p, T> {
         self.advance();
     
         while !self.is_at_end() {
-          if self.previous().token_type == TokenType::SemiColon { return ; };
+          if matches!(self.previous().token_type, TokenType::SemiColon | TokenType::Newline) { return ; };
     
           match self.peek().token_type {
-            TokenType::Class | TokenType::Fun | TokenType::Var |
-            TokenType::For | TokenType::If | TokenType::While |
-            TokenType::Print | TokenType::Return => { return ; },
+            TokenType::Class | TokenType::Enum | TokenType::Trait | TokenType::At | TokenType::Fun | TokenType::Macro | TokenType::Var | TokenType::Const |
+            TokenType::For | TokenType::If | TokenType::While | TokenType::Do | TokenType::Loop | TokenType::Break |
+            TokenType::Match | TokenType::Print | TokenType::Return => { return ; },
             _ => ()
           };
     
@@ -482,8 +1292,22 @@ mod test {
         );
         let mut scanner = Scanner::new(source,  &error_reporter);
         scanner.scan_tokens();
-        let parser = Parser::new(&scanner.tokens, &error_reporter);
+        let parser = Parser::new(&scanner.tokens, &error_reporter, false);
         let Stmt::Expression(ast) = &parser.parse()[0] else {panic!()};
         assert_eq!(pretty_to_string(ast), "(+ (group (- 5 (group (- 3.7 1)))) (- 1.2))");
     }
+
+    #[test]
+    fn pipe_desugars_to_call(){
+        let source = "xs |> map(f) |> sum();";
+        let printer = TestPrinter::default();
+        let error_reporter = ErrorReporter::new(
+            source, false, &printer
+        );
+        let mut scanner = Scanner::new(source,  &error_reporter);
+        scanner.scan_tokens();
+        let parser = Parser::new(&scanner.tokens, &error_reporter, false);
+        let Stmt::Expression(ast) = &parser.parse()[0] else {panic!()};
+        assert_eq!(pretty_to_string(ast), "(call (Var sum) (call (Var map) (Var xs) (Var f)))");
+    }
 }
\ No newline at end of file
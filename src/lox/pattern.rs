@@ -0,0 +1,114 @@
+use std::{fmt::Display, rc::Rc};
+
+use super::{expr::{Expr, Literals}, token::Token};
+
+/// Binding target of a variable declaration, a destructuring assignment
+/// or an arm of match statement.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    pub id: usize,
+    pub pattern_type: PatternType
+}
+
+impl Pattern {
+    fn new(pattern_type: PatternType) -> Self {
+        // Patterns share ids with expressions, so that bindings can be
+        // resolved into the same interpreter locals table.
+        Self { id: Expr::get_inc_expr_id(), pattern_type }
+    }
+
+    /// Token to report errors at for this pattern.
+    pub fn token(&self) -> Rc<Token> {
+        match &self.pattern_type {
+            PatternType::Binding(name) | PatternType::Wildcard(name) => name.clone(),
+            PatternType::Literal(token, _) => token.clone(),
+            PatternType::List(bracket, _)
+            | PatternType::Tuple(bracket, _)
+            | PatternType::Fields(bracket, _) => bracket.clone(),
+            PatternType::Instance { name, class: _, fields: _ } => name.clone(),
+            PatternType::Variant { name: _, enumeration: _, variant, payload: _ } => variant.clone(),
+            PatternType::Alternatives(alternatives) => alternatives[0].token(),
+        }
+    }
+
+    /// All names bound by this pattern in source order, along with the id
+    /// of the binding pattern introducing them.
+    pub fn bindings(&self) -> Vec<(usize, &Rc<Token>)> {
+        match &self.pattern_type {
+            PatternType::Binding(name) => vec![(self.id, name)],
+            PatternType::Literal(_, _) | PatternType::Wildcard(_) => vec![],
+            PatternType::List(_, elements)
+            | PatternType::Tuple(_, elements)
+            | PatternType::Fields(_, elements)
+            | PatternType::Instance { name: _, class: _, fields: elements } => {
+                elements.iter().flat_map(|element| element.bindings()).collect()
+            }
+            PatternType::Variant { name: _, enumeration: _, variant: _, payload } => {
+                payload.iter().flatten().flat_map(|element| element.bindings()).collect()
+            }
+            // Every alternative binds the same names, which resolver makes sure of.
+            PatternType::Alternatives(alternatives) => alternatives[0].bindings(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum PatternType {
+    /// Binding( name )
+    Binding(Rc<Token>),
+    /// List( bracket, elements )
+    List(Rc<Token>, Vec<Pattern>),
+    /// Tuple( paren, elements )
+    Tuple(Rc<Token>, Vec<Pattern>),
+    /// Fields( brace, fields )
+    /// where every field is a Binding named after the property it reads.
+    Fields(Rc<Token>, Vec<Pattern>),
+    /// Literal( token, literal ), only in match arms.
+    Literal(Rc<Token>, Literals),
+    /// Wildcard( underscore ), only in match arms.
+    Wildcard(Rc<Token>),
+    /// Alternatives( patterns ), only in match arms.
+    Alternatives(Vec<Pattern>),
+    /// Instance( class_name, class, fields ), only in match arms.
+    /// Matches instances of class and binds their fields like Fields does.
+    Instance { name: Rc<Token>, class: Box<Expr>, fields: Vec<Pattern> },
+    /// Variant( enum_name, enumeration, variant, payload ), only in match arms.
+    /// Without payload patterns any value of the variant matches.
+    Variant { name: Rc<Token>, enumeration: Box<Expr>, variant: Rc<Token>, payload: Option<Vec<Pattern>> },
+}
+
+impl From<PatternType> for Pattern {
+    fn from(value: PatternType) -> Self {
+        Self::new(value)
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let join = |elements: &Vec<Pattern>| {
+            elements.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(", ")
+        };
+        match &self.pattern_type {
+            PatternType::Binding(name) => write!(f, "{}", name.lexeme),
+            PatternType::List(_, elements) => write!(f, "[{}]", join(elements)),
+            PatternType::Tuple(_, elements) => write!(f, "({})", join(elements)),
+            PatternType::Fields(_, fields) => write!(f, "{{{}}}", join(fields)),
+            PatternType::Literal(_, literal) => write!(f, "{}", literal.repr()),
+            PatternType::Wildcard(_) => write!(f, "_"),
+            PatternType::Alternatives(alternatives) => {
+                let alternatives: Vec<String> = alternatives.iter().map(|a| a.to_string()).collect();
+                write!(f, "{}", alternatives.join(" | "))
+            }
+            PatternType::Instance { name, class: _, fields } => {
+                write!(f, "{}{{{}}}", name.lexeme, join(fields))
+            }
+            PatternType::Variant { name, enumeration: _, variant, payload } => {
+                write!(f, "{}.{}", name.lexeme, variant.lexeme)?;
+                if let Some(payload) = payload {
+                    write!(f, "({})", join(payload))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -63,14 +63,56 @@ fn parenthesize(expression: &Expr) -> String {
         ExprType::Literal(Literals::Nil) => "NIL".to_string(),
         ExprType::Literal(Literals::Function(_)) => "<Function>".to_string(),
         ExprType::Literal(Literals::Instance(_)) => "<Function>".to_string(),
+        ExprType::Literal(Literals::List(_)) => "<List>".to_string(),
+        ExprType::Literal(Literals::Map(_)) => "<Map>".to_string(),
+        ExprType::Literal(
+            literal @ (Literals::Tuple(_) | Literals::Enum(_) | Literals::Variant(_) | Literals::Trait(_))
+        ) => literal.repr(),
         ExprType::Unary(op, right) => {
             format!("({} {})", op.lexeme, parenthesize(right))
         }
         ExprType::Variable(var_name) => format!("(Var {})", var_name.lexeme),
+        ExprType::This(_) => "this".to_string(),
         ExprType::Assign(op, expr) => format!("({} {})", op.lexeme, parenthesize(expr)),
-        ExprType::Call { callee: _, paren: _, arguments: _ } => todo!(),
-        ExprType::Get { object, property } => todo!(),
-        ExprType::Set { object, property, value } => todo!(),
+        ExprType::AssignPattern(target, expr) => format!("({} {})", target, parenthesize(expr)),
+        ExprType::List { bracket: _, elements } => {
+            let elements: Vec<String> = elements.iter().map(parenthesize).collect();
+            format!("(list {})", elements.join(" "))
+        }
+        ExprType::Tuple { paren: _, elements } => {
+            let elements: Vec<String> = elements.iter().map(parenthesize).collect();
+            format!("(tuple {})", elements.join(" "))
+        }
+        ExprType::Map { brace: _, entries } => {
+            let entries: Vec<String> = entries.iter()
+                .map(|(k, v)| format!("({} {})", parenthesize(k), parenthesize(v)))
+                .collect();
+            format!("(map {})", entries.join(" "))
+        }
+        ExprType::Index { object, bracket: _, index } => {
+            format!("(index {} {})", parenthesize(object), parenthesize(index))
+        }
+        ExprType::SetIndex { object, bracket: _, index, value } => {
+            format!("(set-index {} {} {})", parenthesize(object), parenthesize(index), parenthesize(value))
+        }
+        ExprType::Call { callee, paren: _, arguments } => {
+            let arguments: Vec<String> = arguments.iter().map(parenthesize).collect();
+            format!("(call {} {})", parenthesize(callee), arguments.join(" "))
+        }
+        ExprType::Block { brace: _, statements, value } => {
+            let value = value.as_ref().map_or("NIL".to_string(), |value| parenthesize(value));
+            format!("(block <{} statements> {})", statements.len(), value)
+        }
+        ExprType::If { keyword: _, condition, then_branch, else_branch } => {
+            let else_branch = else_branch.as_ref().map_or("NIL".to_string(), |branch| parenthesize(branch));
+            format!("(if {} {} {})", parenthesize(condition), parenthesize(then_branch), else_branch)
+        }
+        ExprType::Get { object, property } => {
+            format!("(get {} {})", parenthesize(object), property.lexeme)
+        }
+        ExprType::Set { object, property, value } => {
+            format!("(set {} {} {})", parenthesize(object), property.lexeme, parenthesize(value))
+        }
     }
 }
 
@@ -0,0 +1,314 @@
+use std::rc::Rc;
+
+use super::{
+    error_reporter::ErrorReporter,
+    expr::{Expr, ExprType, Literals},
+    list::List,
+    map::Map,
+    parser::Parser,
+    pattern::{Pattern, PatternType},
+    printer::Print,
+    scanner::Scanner,
+    stmt::{Decorator, Stmt},
+    token::Token,
+};
+
+/// Scans and parses source given to parse() at runtime. Macros aren't
+/// expanded, as parse() shows code as written.
+pub fn parse_source<T: Print>(source: &str, error_reporter: &ErrorReporter<T>) -> Vec<Stmt> {
+    let mut scanner = Scanner::new(source, error_reporter);
+    scanner.scan_tokens();
+    Parser::new(&scanner.tokens, error_reporter, scanner.optional_semicolons()).parse()
+}
+
+/// Syntax tree as Lox values, returned by parse() native. Every node is a
+/// map with its kind under "type", such as `{"type": "Print", "expression": ...}`,
+/// and its children under other keys. Names and operators are strings,
+/// optional children nil, and lists of children lists.
+pub fn stmts_value(stmts: &[Stmt]) -> Literals {
+    list(stmts.iter().map(stmt_value))
+}
+
+fn stmt_value(stmt: &Stmt) -> Literals {
+    match stmt {
+        Stmt::Expression(expr) => node("Expression", [("expression", expr_value(expr))]),
+        Stmt::Print(expr) => node("Print", [("expression", expr_value(expr))]),
+        Stmt::Function { name, params, body, decorators, signature: _ } => node(
+            "Function",
+            [
+                ("name", name_value(name)),
+                ("params", names_value(params)),
+                ("body", stmts_value(body)),
+                ("decorators", decorators_value(decorators)),
+            ],
+        ),
+        Stmt::If(condition, then_stmt, else_stmt) => node(
+            "If",
+            [
+                ("condition", expr_value(condition)),
+                ("then", stmt_value(then_stmt)),
+                ("else", optional((**else_stmt).as_ref(), stmt_value)),
+            ],
+        ),
+        Stmt::Var(pattern, _, initializer) => node(
+            "Var",
+            [
+                ("pattern", pattern_value(pattern)),
+                ("initializer", optional(initializer.as_ref(), expr_value)),
+            ],
+        ),
+        Stmt::Const(pattern, _, initializer) => node(
+            "Const",
+            [("pattern", pattern_value(pattern)), ("initializer", expr_value(initializer))],
+        ),
+        Stmt::Block(stmts) => node("Block", [("statements", stmts_value(stmts))]),
+        Stmt::While { label, condition, body, do_while } => node(
+            "While",
+            [
+                ("label", optional(label.as_ref(), name_value)),
+                ("condition", expr_value(condition)),
+                ("body", stmt_value(body)),
+                ("doWhile", Literals::Bool(*do_while)),
+            ],
+        ),
+        Stmt::Break { keyword: _, label } => node("Break", [("label", optional(label.as_ref(), name_value))]),
+        Stmt::Match { keyword: _, subject, arms } => node(
+            "Match",
+            [
+                ("subject", expr_value(subject)),
+                (
+                    "arms",
+                    list(arms.iter().map(|arm| {
+                        node(
+                            "MatchArm",
+                            [
+                                ("pattern", pattern_value(&arm.pattern)),
+                                ("guard", optional(arm.guard.as_ref(), expr_value)),
+                                ("body", stmt_value(&arm.body)),
+                            ],
+                        )
+                    })),
+                ),
+            ],
+        ),
+        Stmt::Return { return_keyword: _, expression } => {
+            node("Return", [("value", optional(expression.as_ref(), expr_value))])
+        }
+        Stmt::Class(class) => node(
+            "Class",
+            [
+                ("name", name_value(&class.name)),
+                ("traits", list(class.traits.iter().map(expr_value))),
+                ("methods", stmts_value(&class.methods)),
+                ("getters", stmts_value(&class.getters)),
+                ("setters", stmts_value(&class.setters)),
+                ("classMethods", stmts_value(&class.class_methods)),
+                (
+                    "staticFields",
+                    list(class.static_fields.iter().map(|field| {
+                        node(
+                            "StaticField",
+                            [
+                                ("name", name_value(&field.name)),
+                                ("initializer", optional(field.initializer.as_ref(), expr_value)),
+                            ],
+                        )
+                    })),
+                ),
+            ],
+        ),
+        Stmt::Trait { name, methods, required } => node(
+            "Trait",
+            [
+                ("name", name_value(name)),
+                ("methods", stmts_value(methods)),
+                (
+                    "required",
+                    list(required.iter().map(|method| {
+                        node(
+                            "RequiredMethod",
+                            [("name", name_value(&method.name)), ("params", names_value(&method.params))],
+                        )
+                    })),
+                ),
+            ],
+        ),
+        Stmt::Extend { name, class: _, methods } => {
+            node("Extend", [("name", name_value(name)), ("methods", stmts_value(methods))])
+        }
+        Stmt::Enum { name, variants } => node(
+            "Enum",
+            [
+                ("name", name_value(name)),
+                (
+                    "variants",
+                    list(variants.iter().map(|variant| {
+                        node(
+                            "Variant",
+                            [
+                                ("name", name_value(&variant.name)),
+                                ("fields", optional(variant.fields.as_ref(), |fields| names_value(fields))),
+                            ],
+                        )
+                    })),
+                ),
+            ],
+        ),
+        Stmt::Macro(declaration) => node(
+            "Macro",
+            [
+                ("name", name_value(&declaration.name)),
+                ("params", names_value(&declaration.params)),
+                ("body", expr_value(&declaration.body)),
+            ],
+        ),
+    }
+}
+
+fn expr_value(expr: &Expr) -> Literals {
+    match &expr.expr_type {
+        ExprType::Assign(name, value) => {
+            node("Assign", [("name", name_value(name)), ("value", expr_value(value))])
+        }
+        ExprType::AssignPattern(pattern, value) => node(
+            "AssignPattern",
+            [("pattern", pattern_value(pattern)), ("value", expr_value(value))],
+        ),
+        ExprType::Binary(left, operator, right) => binary_value("Binary", left, operator, right),
+        ExprType::Logical(left, operator, right) => binary_value("Logical", left, operator, right),
+        ExprType::Grouping(inner) => node("Grouping", [("expression", expr_value(inner))]),
+        ExprType::Literal(value) => node("Literal", [("value", value.clone())]),
+        ExprType::Unary(operator, operand) => node(
+            "Unary",
+            [("operator", name_value(operator)), ("operand", expr_value(operand))],
+        ),
+        ExprType::Variable(name) => node("Variable", [("name", name_value(name))]),
+        ExprType::Call { callee, paren: _, arguments } => node(
+            "Call",
+            [
+                ("callee", expr_value(callee)),
+                ("arguments", list(arguments.iter().map(expr_value))),
+            ],
+        ),
+        ExprType::Get { object, property } => node(
+            "Get",
+            [("object", expr_value(object)), ("property", name_value(property))],
+        ),
+        ExprType::This(_) => node("This", []),
+        ExprType::Set { object, property, value } => node(
+            "Set",
+            [
+                ("object", expr_value(object)),
+                ("property", name_value(property)),
+                ("value", expr_value(value)),
+            ],
+        ),
+        ExprType::List { bracket: _, elements } => {
+            node("List", [("elements", list(elements.iter().map(expr_value)))])
+        }
+        ExprType::Tuple { paren: _, elements } => {
+            node("Tuple", [("elements", list(elements.iter().map(expr_value)))])
+        }
+        ExprType::Map { brace: _, entries } => node(
+            "Map",
+            [(
+                "entries",
+                list(entries.iter().map(|(key, value)| list([expr_value(key), expr_value(value)]))),
+            )],
+        ),
+        ExprType::Index { object, bracket: _, index } => node(
+            "Index",
+            [("object", expr_value(object)), ("index", expr_value(index))],
+        ),
+        ExprType::SetIndex { object, bracket: _, index, value } => node(
+            "SetIndex",
+            [
+                ("object", expr_value(object)),
+                ("index", expr_value(index)),
+                ("value", expr_value(value)),
+            ],
+        ),
+        ExprType::Block { brace: _, statements, value } => node(
+            "BlockExpression",
+            [
+                ("statements", stmts_value(statements)),
+                ("value", optional(value.as_deref(), expr_value)),
+            ],
+        ),
+        ExprType::If { keyword: _, condition, then_branch, else_branch } => node(
+            "IfExpression",
+            [
+                ("condition", expr_value(condition)),
+                ("then", expr_value(then_branch)),
+                ("else", optional(else_branch.as_deref(), expr_value)),
+            ],
+        ),
+    }
+}
+
+fn pattern_value(pattern: &Pattern) -> Literals {
+    let patterns = |patterns: &Vec<Pattern>| list(patterns.iter().map(pattern_value));
+    match &pattern.pattern_type {
+        PatternType::Binding(name) => node("Binding", [("name", name_value(name))]),
+        PatternType::List(_, elements) => node("ListPattern", [("elements", patterns(elements))]),
+        PatternType::Tuple(_, elements) => node("TuplePattern", [("elements", patterns(elements))]),
+        PatternType::Fields(_, fields) => node("FieldsPattern", [("fields", patterns(fields))]),
+        PatternType::Literal(_, value) => node("LiteralPattern", [("value", value.clone())]),
+        PatternType::Wildcard(_) => node("Wildcard", []),
+        PatternType::Alternatives(alternatives) => {
+            node("Alternatives", [("alternatives", patterns(alternatives))])
+        }
+        PatternType::Instance { name, class: _, fields } => node(
+            "InstancePattern",
+            [("class", name_value(name)), ("fields", patterns(fields))],
+        ),
+        PatternType::Variant { name, enumeration: _, variant, payload } => node(
+            "VariantPattern",
+            [
+                ("enum", name_value(name)),
+                ("variant", name_value(variant)),
+                ("payload", optional(payload.as_ref(), patterns)),
+            ],
+        ),
+    }
+}
+
+fn binary_value(kind: &str, left: &Expr, operator: &Rc<Token>, right: &Expr) -> Literals {
+    node(
+        kind,
+        [
+            ("operator", name_value(operator)),
+            ("left", expr_value(left)),
+            ("right", expr_value(right)),
+        ],
+    )
+}
+
+fn decorators_value(decorators: &[Decorator]) -> Literals {
+    list(decorators.iter().map(|decorator| expr_value(&decorator.expression)))
+}
+
+fn node<const N: usize>(kind: &str, children: [(&str, Literals); N]) -> Literals {
+    let entries = [("type", Literals::String(kind.to_string()))]
+        .into_iter()
+        .chain(children)
+        .map(|(key, value)| (Literals::String(key.to_string()), value))
+        .collect();
+    Literals::Map(Map::new(entries))
+}
+
+fn list(elements: impl IntoIterator<Item = Literals>) -> Literals {
+    Literals::List(List::new(elements.into_iter().collect()))
+}
+
+fn optional<V>(value: Option<&V>, to_value: impl Fn(&V) -> Literals) -> Literals {
+    value.map_or(Literals::Nil, to_value)
+}
+
+fn name_value(name: &Rc<Token>) -> Literals {
+    Literals::String(name.lexeme.to_string())
+}
+
+fn names_value(names: &[Rc<Token>]) -> Literals {
+    list(names.iter().map(name_value))
+}
@@ -1,25 +1,79 @@
-use std::{collections::HashMap, rc::Rc};
+use std::{collections::{HashMap, HashSet}, rc::Rc};
 
 use super::{
+    builtins,
     error_reporter::ErrorReporter,
-    expr::{Expr, ExprType},
+    expr::{Expr, ExprType, Literals},
     interpreter::Interpreter,
+    pattern::{Pattern, PatternType},
     printer::Print,
-    stmt::Stmt,
+    stmt::{ClassDeclaration, Decorator, EnumVariant, MatchArm, Stmt},
     token::Token,
+    token_type::TokenType,
 };
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 enum FunctionType {
     None,
     Function,
+    Method,
+    Initializer,
 }
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum ClassType {
+    None,
+    Class,
+    Trait,
+    Extension,
+}
+
+/// Variable by its name and index of the scope declaring it, None for globals.
+type VariableKey = (Option<usize>, String);
+
+#[derive(Debug, Clone)]
+struct Binding {
+    defined: bool,
+    mutable: bool,
+    declaration: Rc<Token>,
+}
+
+/// Loop enclosing statements being resolved.
+#[derive(Debug)]
+struct Loop {
+    /// Label of the loop, None for unlabeled loops.
+    label: Option<Rc<Token>>,
+    /// Variables unassigned at some `break` out of the loop, as code after
+    /// the loop is reached from every one of them.
+    break_unassigned: HashSet<VariableKey>,
+}
+
 pub struct Resolver<'a, 'p, T: Print> {
     // pub environment: Environment,
     err_reporter: &'a ErrorReporter<'a, 'p, T>,
     interpreter: &'a mut Interpreter<'p, T>,
-    scopes: Vec<HashMap<String, bool>>,
+    scopes: Vec<HashMap<String, Binding>>,
     current_function: FunctionType,
+    current_class: ClassType,
+    /// Whether statements being resolved are inside a block expression of
+    /// the current function, which can't be returned from.
+    in_block_expression: bool,
+    /// Loops enclosing statements being resolved, innermost last. Loops
+    /// outside of current function or block expression can't be broken out
+    /// of, so they aren't listed.
+    loops: Vec<Loop>,
+    /// Whether strict mode was requested by the host, in addition to the
+    /// `"use strict";` pragma.
+    strict: bool,
+    /// Names of all globals in strict mode: natives, globals of earlier
+    /// runs and top-level declarations of the program. None outside of
+    /// strict mode, where undefined globals are only reported at runtime.
+    globals: Option<HashSet<String>>,
+    /// Variables which aren't definitely assigned at the current point of
+    /// the function being resolved, tracked in strict mode only.
+    unassigned: HashSet<VariableKey>,
+    /// Constants declared by top-level code, which can't be redeclared.
+    global_constants: HashMap<String, Rc<Token>>,
 }
 
 impl<'a, 'p, T: Print> Resolver<'a, 'p, T> {
@@ -32,33 +86,128 @@ impl<'a, 'p, T: Print> Resolver<'a, 'p, T> {
             interpreter,
             scopes: vec![],
             current_function: FunctionType::None,
+            current_class: ClassType::None,
+            in_block_expression: false,
+            loops: vec![],
+            strict: false,
+            globals: None,
+            unassigned: HashSet::new(),
+            global_constants: HashMap::new(),
+        }
+    }
+
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn resolve(&mut self, stmts: &Vec<Stmt>) {
+        if self.strict || Self::has_strict_pragma(stmts) {
+            self.collect_globals(stmts);
+        }
+        self.resolve_stmts(stmts);
+    }
+
+    /// Whether program starts with `"use strict";`.
+    fn has_strict_pragma(stmts: &[Stmt]) -> bool {
+        matches!(
+            stmts.first(),
+            Some(Stmt::Expression(Expr { expr_type: ExprType::Literal(Literals::String(pragma)), .. }))
+                if pragma == "use strict"
+        )
+    }
+
+    /// Collects names of globals for strict mode. Globals declared by the
+    /// program aren't assigned until their declaration runs.
+    fn collect_globals(&mut self, stmts: &[Stmt]) {
+        let defined = self.interpreter.environment.globals.borrow().values.clone();
+        let mut globals: HashSet<String> = defined.keys().cloned().collect();
+        for stmt in stmts {
+            let names = match stmt {
+                Stmt::Var(pattern, _, _) | Stmt::Const(pattern, _, _) => {
+                    pattern.bindings().into_iter().map(|(_, name)| name.clone()).collect()
+                }
+                Stmt::Function { name, .. }
+                | Stmt::Trait { name, .. }
+                | Stmt::Enum { name, .. }
+                | Stmt::Class(ClassDeclaration { name, .. }) => vec![name.clone()],
+                _ => vec![],
+            };
+            for name in names {
+                if !defined.contains_key(&name.lexeme) {
+                    self.unassigned.insert((None, name.lexeme.to_string()));
+                }
+                globals.insert(name.lexeme.to_string());
+            }
         }
+        // Globals of earlier runs declared without a value.
+        for (name, value) in defined {
+            if value.is_none() {
+                self.unassigned.insert((None, name));
+            }
+        }
+        self.globals = Some(globals);
     }
 
-    pub fn resolve(&mut self, stmts: &'a Vec<Stmt>) {
+    fn resolve_stmts(&mut self, stmts: &Vec<Stmt>) {
         for stmt in stmts {
             self.resolve_stmt(stmt);
         }
     }
 
-    fn resolve_stmt(&mut self, stmt: &'a Stmt) {
+    fn resolve_stmt(&mut self, stmt: &Stmt) {
         match stmt {
             Stmt::Expression(expr) => self.resolve_expr(expr),
-            Stmt::Function { name, params, body } => {
+            Stmt::Function { name, params, body, decorators, signature: _ } => {
+                self.resolve_decorators(decorators);
                 self.resolve_function_declaration(name, params, body)
             }
             Stmt::If(condtion, then_stmt, else_stmt) => {
-                self.resolve_if_stmt(condtion, then_stmt, else_stmt)
+                self.resolve_if_stmt(condtion, then_stmt, else_stmt.as_ref().as_ref())
             }
             Stmt::Print(expr) => self.resolve_print_stmt(expr),
-            Stmt::Var(name, expr) => self.resolve_var_stmt(name, expr),
+            Stmt::Var(pattern, _annotation, expr) => self.resolve_var_stmt(pattern, expr.as_ref(), true),
+            Stmt::Const(pattern, _annotation, expr) => self.resolve_var_stmt(pattern, Some(expr), false),
             Stmt::Block(stmts) => self.resolve_block_stmt(stmts),
-            Stmt::While(condition, body) => self.resolve_while_stmt(condition, body),
+            Stmt::While { label, condition, body, do_while } => {
+                self.resolve_while_stmt(label.as_ref(), condition, body, *do_while)
+            }
+            Stmt::Break { keyword, label } => self.resolve_break_stmt(keyword, label.as_ref()),
+            Stmt::Match {
+                keyword: _,
+                subject,
+                arms,
+            } => self.resolve_match_stmt(subject, arms),
             Stmt::Return {
                 return_keyword,
                 expression,
             } => self.resolve_return_stmt(return_keyword, expression),
-            Stmt::Class { name, methods } => self.resolve_class_stmt(name),
+            Stmt::Class(class) => self.resolve_class_stmt(class),
+            Stmt::Trait {
+                name,
+                methods,
+                required: _,
+            } => self.resolve_trait_stmt(name, methods),
+            Stmt::Enum { name, variants } => self.resolve_enum_stmt(name, variants),
+            Stmt::Extend { name, class, methods } => {
+                if !builtins::TYPE_NAMES.contains(&name.lexeme.as_str()) {
+                    self.resolve_expr(class);
+                }
+                self.resolve_methods(name, methods, [].iter(), ClassType::Extension);
+                for (i, method) in methods.iter().enumerate() {
+                    let Stmt::Function { name: method_name, .. } = method else { continue };
+                    let declared_before = methods[..i].iter().any(|earlier| {
+                        matches!(earlier, Stmt::Function { name, .. } if name.lexeme == method_name.lexeme)
+                    });
+                    if declared_before {
+                        self.err_reporter.error_token(
+                            method_name.clone(),
+                            "Already a method with this name in this extension",
+                        )
+                    }
+                }
+            }
+            Stmt::Macro(_) => unreachable!("Macros are expanded before resolving"),
         }
     }
 
@@ -68,62 +217,200 @@ impl<'a, 'p, T: Print> Resolver<'a, 'p, T> {
 
     fn end_scope(&mut self) {
         self.scopes.pop();
+        let depth = self.scopes.len();
+        self.unassigned.retain(|(scope, _)| *scope != Some(depth));
     }
 
     fn declare(&mut self, name: &Rc<Token>) {
         if self.scopes.is_empty() {
+            if let Some(constant) = self.global_constants.get(&name.lexeme) {
+                self.err_reporter.error_token(
+                    name.clone(),
+                    format!("Can't redeclare constant '{}'", name.lexeme).as_str(),
+                );
+                self.err_reporter.note_token(
+                    constant.clone(),
+                    format!("'{}' is declared constant here", name.lexeme).as_str(),
+                );
+            }
             return;
         }
-        self.scopes
+        self.scopes.last_mut().unwrap().insert(
+            name.lexeme.to_string(),
+            Binding {
+                defined: false,
+                mutable: true,
+                declaration: name.clone(),
+            },
+        );
+    }
+
+    fn declare_constant(&mut self, name: &Rc<Token>) {
+        self.declare(name);
+        if self.scopes.is_empty() {
+            self.global_constants.insert(name.lexeme.to_string(), name.clone());
+        }
+        if let Some(binding) = self
+            .scopes
             .last_mut()
-            .unwrap()
-            .insert(name.lexeme.to_string(), false);
+            .and_then(|scope| scope.get_mut(&name.lexeme))
+        {
+            binding.mutable = false;
+        }
     }
 
     fn define(&mut self, name: &Rc<Token>) {
+        self.mark_assigned(name, true);
         if self.scopes.is_empty() {
             return;
         }
         self.scopes
             .last_mut()
             .unwrap()
-            .insert(name.lexeme.to_string(), true);
+            .entry(name.lexeme.to_string())
+            .or_insert_with(|| Binding {
+                defined: false,
+                mutable: true,
+                declaration: name.clone(),
+            })
+            .defined = true;
     }
 
-    fn resolve_block_stmt(&mut self, stmts: &'a Vec<Stmt>) {
+    fn resolve_block_stmt(&mut self, stmts: &Vec<Stmt>) {
         self.begin_scope();
-        self.resolve(stmts);
+        self.resolve_stmts(stmts);
         self.end_scope();
     }
 
-    fn resolve_class_stmt(&mut self, name: &Rc<Token>) {
+    fn resolve_class_stmt(&mut self, class: &ClassDeclaration) {
+        self.declare(&class.name);
+        self.define(&class.name);
+        // Traits and static field initializers are evaluated in the scope enclosing the class.
+        for trait_expr in &class.traits {
+            self.resolve_expr(trait_expr);
+        }
+        for field in &class.static_fields {
+            if let Some(initializer) = &field.initializer {
+                self.resolve_expr(initializer);
+            }
+        }
+
+        // Getters, setters and class methods are never initializers.
+        let other_methods = class
+            .getters
+            .iter()
+            .chain(&class.setters)
+            .chain(&class.class_methods);
+        self.resolve_methods(&class.name, &class.methods, other_methods, ClassType::Class);
+    }
+
+    fn resolve_trait_stmt(&mut self, name: &Rc<Token>, methods: &[Stmt]) {
         self.declare(name);
         self.define(name);
+        self.resolve_methods(name, &[], methods.iter(), ClassType::Trait);
     }
 
-    fn resolve_var_stmt(&mut self, name: &Rc<Token>, expr: &Option<Expr>) {
-        if self
-            .scopes
-            .last()
-            .is_some_and(|scope| scope.contains_key(&name.lexeme))
-        {
-            self.err_reporter.error_token(
-                name.clone(),
-                "Already a variable with this name in this scope",
-            )
+    fn resolve_methods<'s>(
+        &mut self,
+        declaration: &Rc<Token>,
+        methods: &'s [Stmt],
+        other_methods: impl Iterator<Item = &'s Stmt>,
+        class_type: ClassType,
+    ) {
+        // Decorators are evaluated where the class is declared.
+        for method in methods {
+            if let Stmt::Function { decorators, .. } = method {
+                self.resolve_decorators(decorators);
+            }
+        }
+        let enclosing_class = self.current_class;
+        self.current_class = class_type;
+        // 'this' is bound in a scope between the class and method bodies. Inside
+        // class methods it refers to the class itself.
+        self.begin_scope();
+        self.scopes.last_mut().unwrap().insert(
+            "this".to_string(),
+            Binding {
+                defined: true,
+                mutable: false,
+                declaration: declaration.clone(),
+            },
+        );
+        for method in methods {
+            let Stmt::Function { name, params, body, decorators: _, signature: _ } = method else {
+                unreachable!("Class body only contains method declarations")
+            };
+            let function_type = if name.lexeme == "init" {
+                FunctionType::Initializer
+            } else {
+                FunctionType::Method
+            };
+            self.resolve_function(params, body, function_type);
         }
+        for method in other_methods {
+            let Stmt::Function { name: _, params, body, decorators: _, signature: _ } = method else {
+                unreachable!("Class body only contains method declarations")
+            };
+            self.resolve_function(params, body, FunctionType::Method);
+        }
+        self.end_scope();
+        self.current_class = enclosing_class;
+    }
+
+    fn resolve_enum_stmt(&mut self, name: &Rc<Token>, variants: &[EnumVariant]) {
+        // Variants are accessed as members of the enum, so only the enum
+        // name itself is a variable.
         self.declare(name);
+        self.define(name);
+        for (i, variant) in variants.iter().enumerate() {
+            if variants[..i].iter().any(|v| v.name.lexeme == variant.name.lexeme) {
+                self.err_reporter.error_token(
+                    variant.name.clone(),
+                    "Already a variant with this name in this enum",
+                )
+            }
+        }
+    }
+
+    fn resolve_var_stmt(&mut self, pattern: &Pattern, expr: Option<&Expr>, mutable: bool) {
+        let bindings = pattern.bindings();
+        for (_, name) in &bindings {
+            if self
+                .scopes
+                .last()
+                .is_some_and(|scope| scope.contains_key(&name.lexeme))
+            {
+                self.err_reporter.error_token(
+                    (*name).clone(),
+                    "Already a variable with this name in this scope",
+                )
+            }
+            if mutable {
+                self.declare(name);
+            } else {
+                self.declare_constant(name);
+            }
+        }
         if let Some(expr) = expr {
             self.resolve_expr(expr);
         }
-        self.define(name);
+        for (_, name) in &bindings {
+            self.define(name);
+            self.mark_assigned(name, expr.is_some());
+        }
+    }
+
+    fn resolve_decorators(&mut self, decorators: &[Decorator]) {
+        for decorator in decorators {
+            self.resolve_expr(&decorator.expression);
+        }
     }
 
     fn resolve_function_declaration(
         &mut self,
         name: &Rc<Token>,
         params: &Vec<Rc<Token>>,
-        body: &'a Vec<Stmt>,
+        body: &Vec<Stmt>,
     ) {
         self.declare(name);
         self.define(name);
@@ -133,32 +420,48 @@ impl<'a, 'p, T: Print> Resolver<'a, 'p, T> {
     fn resolve_function(
         &mut self,
         params: &Vec<Rc<Token>>,
-        body: &'a Vec<Stmt>,
+        body: &Vec<Stmt>,
         function_type: FunctionType,
     ) {
         let encloing_function = self.current_function;
         self.current_function = function_type;
+        let enclosing_block_expression = std::mem::replace(&mut self.in_block_expression, false);
+        let enclosing_loops = std::mem::take(&mut self.loops);
+        // Function may run after variables of enclosing functions are
+        // assigned, and its assignments may never run.
+        let enclosing_unassigned = std::mem::take(&mut self.unassigned);
         self.begin_scope();
         for param in params {
             self.declare(param);
             self.define(param);
         }
-        self.resolve(body);
+        self.resolve_stmts(body);
         self.end_scope();
+        self.unassigned = enclosing_unassigned;
+        self.loops = enclosing_loops;
+        self.in_block_expression = enclosing_block_expression;
         self.current_function = encloing_function;
     }
 
     fn resolve_if_stmt(
         &mut self,
         condition: &Expr,
-        then_stmt: &'a Stmt,
-        else_stmt: &'a Option<Stmt>,
+        then_stmt: &Stmt,
+        else_stmt: Option<&Stmt>,
     ) {
         self.resolve_expr(condition);
+        let before = self.unassigned.clone();
         self.resolve_stmt(then_stmt);
+        let after_then = std::mem::replace(&mut self.unassigned, before);
         if let Some(else_stmt) = else_stmt {
             self.resolve_stmt(else_stmt);
         }
+        // Branch leaving the function or loop doesn't reach code after the if.
+        if else_stmt.is_some_and(Stmt::diverges) {
+            self.unassigned = after_then;
+        } else if !then_stmt.diverges() {
+            self.unassigned.extend(after_then);
+        }
     }
 
     fn resolve_print_stmt(&mut self, expr: &Expr) {
@@ -169,15 +472,173 @@ impl<'a, 'p, T: Print> Resolver<'a, 'p, T> {
         if self.current_function == FunctionType::None {
             self.err_reporter
                 .error_token(return_keyword.clone(), "Can't return from top-level code")
+        } else if self.in_block_expression {
+            self.err_reporter
+                .error_token(return_keyword.clone(), "Can't return from inside a block expression")
         }
         if let Some(expr) = expr {
+            if self.current_function == FunctionType::Initializer {
+                self.err_reporter
+                    .error_token(return_keyword.clone(), "Can't return a value from an initializer")
+            }
             self.resolve_expr(expr)
         }
     }
 
-    fn resolve_while_stmt(&mut self, condition: &Expr, body: &'a Stmt) {
+    fn resolve_while_stmt(
+        &mut self,
+        label: Option<&Rc<Token>>,
+        condition: &Expr,
+        body: &Stmt,
+        do_while: bool,
+    ) {
+        if let Some(label) = label {
+            if let Some(enclosing) = self.find_label(label) {
+                self.err_reporter.error_token(
+                    label.clone(),
+                    &format!("Label '{}' shadows label of enclosing loop", label.lexeme),
+                );
+                self.err_reporter.note_token(
+                    enclosing,
+                    &format!("'{}' is declared here", label.lexeme),
+                );
+            }
+        }
         self.resolve_expr(condition);
+        let before = self.unassigned.clone();
+        self.loops.push(Loop { label: label.cloned(), break_unassigned: HashSet::new() });
         self.resolve_stmt(body);
+        let enclosed = self.loops.pop().expect("Loop pushed above");
+        // Loop with `true` condition is only left through break. Otherwise
+        // it ends when the condition is false, and body of while loop may
+        // never run.
+        if matches!(condition.expr_type, ExprType::Literal(Literals::Bool(true))) {
+            self.unassigned = enclosed.break_unassigned;
+        } else {
+            if !do_while {
+                self.unassigned.extend(before);
+            }
+            self.unassigned.extend(enclosed.break_unassigned);
+        }
+    }
+
+    fn resolve_break_stmt(&mut self, keyword: &Rc<Token>, label: Option<&Rc<Token>>) {
+        if self.loops.is_empty() {
+            let message = if self.in_block_expression {
+                "Can't break out of a block expression"
+            } else {
+                "Can't break outside of a loop"
+            };
+            self.err_reporter.error_token(keyword.clone(), message);
+            return;
+        }
+        let target = match label {
+            Some(label) => self.loops.iter_mut().rev().find(|enclosing| {
+                enclosing.label.as_ref().is_some_and(|name| name.lexeme == label.lexeme)
+            }),
+            None => self.loops.last_mut(),
+        };
+        match target {
+            Some(target) => target.break_unassigned.extend(self.unassigned.iter().cloned()),
+            None => {
+                let label = label.expect("Innermost loop exists");
+                self.err_reporter.error_token(
+                    label.clone(),
+                    &format!("Undefined label '{}'", label.lexeme),
+                );
+            }
+        }
+    }
+
+    fn find_label(&self, label: &Rc<Token>) -> Option<Rc<Token>> {
+        self.loops.iter()
+            .filter_map(|enclosing| enclosing.label.as_ref())
+            .find(|name| name.lexeme == label.lexeme)
+            .cloned()
+    }
+
+    fn resolve_match_stmt(&mut self, subject: &Expr, arms: &Vec<MatchArm>) {
+        self.resolve_expr(subject);
+        // Value matching no arm is a runtime error, so some arm always runs.
+        let before = self.unassigned.clone();
+        let mut after = HashSet::new();
+        for arm in arms {
+            self.unassigned = before.clone();
+            // Bindings of every arm live in their own scope, shared by guard and body.
+            self.begin_scope();
+            self.resolve_pattern(&arm.pattern);
+            for (_, name) in arm.pattern.bindings() {
+                if self
+                    .scopes
+                    .last()
+                    .is_some_and(|scope| scope.contains_key(&name.lexeme))
+                {
+                    self.err_reporter.error_token(
+                        name.clone(),
+                        "Already a variable with this name in this pattern",
+                    )
+                }
+                self.declare(name);
+                self.define(name);
+            }
+            if let Some(guard) = &arm.guard {
+                self.resolve_expr(guard);
+            }
+            self.resolve_stmt(&arm.body);
+            self.end_scope();
+            if !arm.body.diverges() {
+                after.extend(std::mem::take(&mut self.unassigned));
+            }
+        }
+        self.unassigned = after;
+    }
+
+    /// Resolves class names referenced in pattern and makes sure that
+    /// all alternatives bind the same variables.
+    fn resolve_pattern(&mut self, pattern: &Pattern) {
+        match &pattern.pattern_type {
+            PatternType::Binding(_) | PatternType::Literal(_, _) | PatternType::Wildcard(_) => (),
+            PatternType::List(_, elements)
+            | PatternType::Tuple(_, elements)
+            | PatternType::Fields(_, elements) => {
+                for element in elements {
+                    self.resolve_pattern(element);
+                }
+            }
+            PatternType::Instance { name: _, class, fields } => {
+                self.resolve_expr(class);
+                for field in fields {
+                    self.resolve_pattern(field);
+                }
+            }
+            PatternType::Variant { name: _, enumeration, variant: _, payload } => {
+                self.resolve_expr(enumeration);
+                for element in payload.iter().flatten() {
+                    self.resolve_pattern(element);
+                }
+            }
+            PatternType::Alternatives(alternatives) => {
+                let binding_names = |pattern: &Pattern| {
+                    let mut names: Vec<String> = pattern
+                        .bindings()
+                        .iter()
+                        .map(|(_, name)| name.lexeme.to_string())
+                        .collect();
+                    names.sort();
+                    names
+                };
+                let expected = binding_names(&alternatives[0]);
+                for alternative in alternatives {
+                    self.resolve_pattern(alternative);
+                    if binding_names(alternative) != expected {
+                        self.err_reporter.error_token(
+                            alternative.token(),
+                            "All alternatives of a pattern must bind the same variables",
+                        )
+                    }
+                }
+            }
+        }
     }
 
     fn resolve_expr(&mut self, expr: &Expr) {
@@ -185,19 +646,75 @@ impl<'a, 'p, T: Print> Resolver<'a, 'p, T> {
             ExprType::Assign(var_name, value_expr) => {
                 self.resolve_assign_expr(var_name, value_expr, expr)
             }
+            ExprType::AssignPattern(target, value_expr) => {
+                self.resolve_assign_pattern_expr(target, value_expr)
+            }
             ExprType::Binary(left, _op, right) => self.resolve_binary_expr(left, right),
             ExprType::Grouping(expr) => self.resolve_expr(expr),
             ExprType::Literal(_) => (),
             ExprType::Logical(left, _op, right) => self.resolve_logical_expr(left, right),
             ExprType::Unary(_op, expr) => self.resolve_expr(expr),
             ExprType::Variable(name) => self.resolve_var_expr(name, expr),
+            ExprType::This(keyword) => {
+                if self.current_class == ClassType::None {
+                    self.err_reporter
+                        .error_token(keyword.clone(), "Can't use 'this' outside of a class");
+                    return;
+                }
+                self.resolve_local(keyword, expr.id)
+            }
             ExprType::Call {
                 callee,
                 paren: _,
                 arguments,
             } => self.resolve_call_expr(callee, arguments),
-            ExprType::Get { object, property: _ } => self.resove_get_accessor_expr(object),
-            ExprType::Set { object, property: _, value } => self.resolve_set_expr(object, value),
+            ExprType::Get { object, property } => {
+                self.check_private_access(object, property);
+                self.resove_get_accessor_expr(object)
+            }
+            ExprType::Set { object, property, value } => {
+                self.check_private_access(object, property);
+                self.resolve_set_expr(object, value)
+            }
+            ExprType::List { bracket: _, elements }
+            | ExprType::Tuple { paren: _, elements } => self.resolve_elements(elements),
+            ExprType::Map { brace: _, entries } => {
+                for (key, value) in entries {
+                    self.resolve_expr(key);
+                    self.resolve_expr(value);
+                }
+            }
+            ExprType::Index { object, bracket: _, index } => {
+                self.resolve_expr(object);
+                self.resolve_expr(index);
+            }
+            ExprType::SetIndex { object, bracket: _, index, value } => {
+                self.resolve_expr(value);
+                self.resolve_expr(object);
+                self.resolve_expr(index);
+            }
+            ExprType::Block { brace: _, statements, value } => {
+                let enclosing_block_expression = std::mem::replace(&mut self.in_block_expression, true);
+                let enclosing_loops = std::mem::take(&mut self.loops);
+                self.begin_scope();
+                self.resolve_stmts(statements);
+                if let Some(value) = value {
+                    self.resolve_expr(value);
+                }
+                self.end_scope();
+                self.loops = enclosing_loops;
+                self.in_block_expression = enclosing_block_expression;
+            }
+            ExprType::If { keyword: _, condition, then_branch, else_branch } => {
+                self.resolve_expr(condition);
+                let before = self.unassigned.clone();
+                self.resolve_expr(then_branch);
+                let after_then = std::mem::replace(&mut self.unassigned, before);
+                if let Some(else_branch) = else_branch {
+                    self.resolve_expr(else_branch);
+                }
+                self.unassigned.extend(after_then);
+            }
         }
     }
 
@@ -208,19 +725,55 @@ impl<'a, 'p, T: Print> Resolver<'a, 'p, T> {
                 .last()
                 .unwrap()
                 .get(&name.lexeme)
-                .is_some_and(|defined| !(*defined))
+                .is_some_and(|binding| !binding.defined)
         {
             self.err_reporter.error_token(
                 name.clone(),
                 "Can't read local variables in its own declaration",
             )
+        } else if self.unassigned.contains(&self.variable_key(name)) {
+            self.err_reporter.error_token(
+                name.clone(),
+                &format!("Can't read '{}' before it is assigned", name.lexeme),
+            )
         }
         self.resolve_local(name, expr.id);
     }
 
     fn resolve_assign_expr(&mut self, name: &Rc<Token>, value_expr: &Expr, assign_expr: &Expr) {
         self.resolve_expr(value_expr);
+        self.check_mutable(name);
         self.resolve_local(name, assign_expr.id);
+        self.mark_assigned(name, true);
+    }
+
+    fn resolve_assign_pattern_expr(&mut self, target: &Pattern, value_expr: &Expr) {
+        self.resolve_expr(value_expr);
+        for (binding_id, name) in target.bindings() {
+            self.check_mutable(name);
+            self.resolve_local(name, binding_id);
+            self.mark_assigned(name, true);
+        }
+    }
+
+    /// Reports assignment to a local constant. Global constants are only
+    /// known at runtime and are checked by the environment.
+    fn check_mutable(&self, name: &Rc<Token>) {
+        let binding = self
+            .scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(&name.lexeme));
+        if let Some(binding) = binding.filter(|binding| !binding.mutable) {
+            self.err_reporter.error_token(
+                name.clone(),
+                format!("Can't assign to constant '{}'", name.lexeme).as_str(),
+            );
+            self.err_reporter.note_token(
+                binding.declaration.clone(),
+                format!("'{}' is declared constant here", name.lexeme).as_str(),
+            );
+        }
     }
 
     fn resolve_binary_expr(&mut self, left: &Expr, right: &Expr) {
@@ -244,9 +797,41 @@ impl<'a, 'p, T: Print> Resolver<'a, 'p, T> {
         self.resolve_expr(object);
     }
 
+    /// Private members are only accessible through 'this' in methods of a class.
+    fn check_private_access(&self, object: &Expr, property: &Rc<Token>) {
+        if property.token_type != TokenType::PrivateIdentifier {
+            return;
+        }
+        if !matches!(object.expr_type, ExprType::This(_)) {
+            self.err_reporter.error_token(
+                property.clone(),
+                &format!("Private member '{}' can only be accessed through 'this'", property.lexeme),
+            );
+        } else if self.current_class == ClassType::Trait {
+            self.err_reporter.error_token(
+                property.clone(),
+                &format!("Can't access private member '{}' in trait methods", property.lexeme),
+            );
+        } else if self.current_class == ClassType::Extension {
+            self.err_reporter.error_token(
+                property.clone(),
+                &format!("Can't access private member '{}' in extension methods", property.lexeme),
+            );
+        }
+    }
+
+    fn resolve_elements(&mut self, elements: &Vec<Expr>) {
+        for element in elements {
+            self.resolve_expr(element);
+        }
+    }
+
     fn resolve_logical_expr(&mut self, left: &Expr, right: &Expr) {
         self.resolve_expr(left);
+        // Right operand may never run.
+        let before = self.unassigned.clone();
         self.resolve_expr(right);
+        self.unassigned.extend(before);
     }
 
     fn resolve_local(&mut self, name: &Rc<Token>, expr_id: usize) {
@@ -261,5 +846,81 @@ impl<'a, 'p, T: Print> Resolver<'a, 'p, T> {
                 return;
             }
         }
+        self.check_global(name);
+    }
+
+    /// Reports global missing in strict mode, suggesting visible variable
+    /// with the closest name.
+    fn check_global(&self, name: &Rc<Token>) {
+        let Some(globals) = &self.globals else {
+            return;
+        };
+        if globals.contains(&name.lexeme) {
+            return;
+        }
+        let mut message = format!("Undefined variable '{}'", name.lexeme);
+        let suggestion = self
+            .scopes
+            .iter()
+            .flat_map(|scope| scope.keys())
+            .chain(globals)
+            .filter(|candidate| *candidate != "this")
+            .map(|candidate| (edit_distance(&name.lexeme, candidate), candidate))
+            .filter(|(distance, _)| *distance <= name.lexeme.chars().count() / 3 + 1)
+            .min();
+        if let Some((_, candidate)) = suggestion {
+            message.push_str(&format!(", did you mean '{candidate}'"));
+        }
+        self.err_reporter.error_token(name.clone(), &message);
+    }
+
+    fn variable_key(&self, name: &Rc<Token>) -> VariableKey {
+        let depth = self
+            .scopes
+            .iter()
+            .rposition(|scope| scope.contains_key(&name.lexeme));
+        (depth, name.lexeme.to_string())
+    }
+
+    /// Tracks whether variable is definitely assigned, in strict mode.
+    fn mark_assigned(&mut self, name: &Rc<Token>, assigned: bool) {
+        if self.globals.is_none() {
+            return;
+        }
+        let key = self.variable_key(name);
+        if assigned {
+            self.unassigned.remove(&key);
+        } else {
+            self.unassigned.insert(key);
+        }
+    }
+}
+
+/// Number of single character insertions, deletions and substitutions
+/// turning one string into the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::edit_distance;
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("count", "count"), 0);
+        assert_eq!(edit_distance("cont", "count"), 1);
+        assert_eq!(edit_distance("pritn", "print"), 2);
+        assert_eq!(edit_distance("", "abc"), 3);
     }
 }
@@ -8,22 +8,37 @@ use super::{error_reporter::ErrorReporter, token::Token, printer::Print};
 
 const KEYWORDS: phf::Map<&str, TokenType> = phf_map! {
     "and" =>    TokenType::And,
+    "break" =>  TokenType::Break,
     "class" =>  TokenType::Class,
+    "const" =>  TokenType::Const,
+    "do" =>     TokenType::Do,
     "else" =>   TokenType::Else,
+    "enum" =>   TokenType::Enum,
     "false" =>  TokenType::False,
     "for" =>    TokenType::For,
     "fun" =>    TokenType::Fun,
     "if" =>     TokenType::If,
+    "in" =>     TokenType::In,
+    "is" =>     TokenType::Is,
+    "loop" =>   TokenType::Loop,
+    "macro" =>  TokenType::Macro,
+    "match" =>  TokenType::Match,
     "nil" =>    TokenType::Nil,
     "or" =>     TokenType::Or,
     "print" =>  TokenType::Print,
     "return" => TokenType::Return,
+    "static" => TokenType::Static,
     "super" =>  TokenType::Super,
     "this" =>   TokenType::This,
+    "trait" =>  TokenType::Trait,
     "true" =>   TokenType::True,
+    "typeof" => TokenType::Typeof,
     "var" =>    TokenType::Var,
     "while" =>  TokenType::While,
 };
+/// Pragma at the start of a script enabling optional semicolons.
+const OPTIONAL_SEMICOLONS_PRAGMA: &str = "\"use optional semicolons\"";
+
 pub struct Scanner<'a, 'p, T: Print> {
     source: &'a str,
     source_iter: Peekable<Chars<'a>>,
@@ -32,6 +47,13 @@ pub struct Scanner<'a, 'p, T: Print> {
     line: usize,
     pub tokens: Vec<Rc<Token>>,
     err_reporter: &'a ErrorReporter<'a, 'p, T>,
+    /// Whether line ends are emitted as Newline tokens ending statements.
+    optional_semicolons: bool,
+    /// Brackets open at current position. Line ends inside '(' and '['
+    /// never end a statement.
+    open_brackets: Vec<TokenType>,
+    /// Doc comment read since the last token, attached to the next one.
+    doc: Option<String>,
 }
 
 impl<'a, 'p, T: Print> Scanner<'a, 'p, T> {
@@ -44,17 +66,76 @@ impl<'a, 'p, T: Print> Scanner<'a, 'p, T> {
             line: 1,
             tokens: vec![],
             err_reporter,
+            optional_semicolons: false,
+            open_brackets: vec![],
+            doc: None,
         }
     }
 
+    /// Makes line ends terminate statements, as if followed by ';'. Scripts
+    /// can also enable this themselves, by starting with the pragma
+    /// `"use optional semicolons";`.
+    pub fn with_optional_semicolons(mut self, enabled: bool) -> Self {
+        self.optional_semicolons = enabled;
+        self
+    }
+
+    /// Whether line ends are terminators, as set by `with_optional_semicolons`
+    /// or the pragma. Known once tokens are scanned.
+    pub fn optional_semicolons(&self) -> bool {
+        self.optional_semicolons
+    }
+
     pub fn scan_tokens(&mut self) {
+        if self.source.trim_start().starts_with(OPTIONAL_SEMICOLONS_PRAGMA) {
+            self.optional_semicolons = true;
+        }
         while self.source_iter.peek().is_some() {
             self.start = self.current;
             self.scan_token();
         }
+        if self.optional_semicolons {
+            // Last line always ends with a terminator.
+            self.start = self.current;
+            if self.tokens.last().is_none_or(|token| token.token_type != TokenType::Newline) {
+                self.add_token(TokenType::Newline);
+            }
+            self.remove_continued_newlines();
+        }
         self.tokens.push(Rc::new(Token::new(TokenType::Eof, "".to_string(), self.line)));
     }
 
+    /// Whether line ending at current position ends a statement: it's not
+    /// inside parentheses or brackets, and its last token can end an expression.
+    /// Lines ending in operators, commas or open brackets continue on next line.
+    fn line_ends_statement(&self) -> bool {
+        if self.open_brackets.last().is_some_and(|bracket| *bracket != TokenType::LeftBrace) {
+            return false;
+        }
+        self.tokens.last().is_some_and(|token| matches!(
+            token.token_type,
+            TokenType::Identifier | TokenType::PrivateIdentifier | TokenType::String(_) | TokenType::Number(_)
+            | TokenType::True | TokenType::False | TokenType::Nil | TokenType::This | TokenType::Super
+            | TokenType::RightParen | TokenType::RightBracket | TokenType::Return | TokenType::Break
+        ))
+    }
+
+    /// Drops line ends followed by a token continuing the statement: closing
+    /// brace, 'else', or '.' and '|>' starting a chained call.
+    fn remove_continued_newlines(&mut self) {
+        let tokens = std::mem::take(&mut self.tokens);
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(token) = tokens.next() {
+            let continued = token.token_type == TokenType::Newline && tokens.peek().is_some_and(|next| matches!(
+                next.token_type,
+                TokenType::RightBrace | TokenType::Else | TokenType::Dot | TokenType::PipeGreater
+            ));
+            if !continued {
+                self.tokens.push(token);
+            }
+        }
+    }
+
     fn scan_token(&mut self) {
         let c = self.advance();
         match c {
@@ -63,12 +144,30 @@ impl<'a, 'p, T: Print> Scanner<'a, 'p, T> {
             ')' => self.add_token(TokenType::RightParen),
             '{' => self.add_token(TokenType::LeftBrace),
             '}' => self.add_token(TokenType::RightBrace),
+            '[' => self.add_token(TokenType::LeftBracket),
+            ']' => self.add_token(TokenType::RightBracket),
             ',' => self.add_token(TokenType::Comma),
+            ':' => self.add_token(TokenType::Colon),
+            '@' => self.add_token(TokenType::At),
             '.' => self.add_token(TokenType::Dot),
-            '-' => self.add_token(TokenType::Minus),
+            '-' => {
+                if self.r#match('>') {
+                    self.add_token(TokenType::Arrow)
+                } else {
+                    self.add_token(TokenType::Minus)
+                }
+            }
+            '?' => self.add_token(TokenType::Question),
             '+' => self.add_token(TokenType::Plus),
             ';' => self.add_token(TokenType::SemiColon),
             '*' => self.add_token(TokenType::Star),
+            '|' => {
+                if self.r#match('>') {
+                    self.add_token(TokenType::PipeGreater)
+                } else {
+                    self.add_token(TokenType::Pipe)
+                }
+            }
             //
             '!' => {
                 if self.r#match('=') {
@@ -80,6 +179,8 @@ impl<'a, 'p, T: Print> Scanner<'a, 'p, T> {
             '=' => {
                 if self.r#match('=') {
                     self.add_token(TokenType::EqualEqual)
+                } else if self.r#match('>') {
+                    self.add_token(TokenType::FatArrow)
                 } else {
                     self.add_token(TokenType::Equal)
                 }
@@ -101,9 +202,16 @@ impl<'a, 'p, T: Print> Scanner<'a, 'p, T> {
             //
             '/' => {
                 if self.r#match('/') {
+                    let is_doc = self.source_iter.peek() == Some(&'/') && self.peek_second() != Some('/');
+                    let mut text = String::new();
                     while self.source_iter.peek().is_some_and(|c| *c != '\n') {
-                        self.advance();
+                        text.push(self.advance());
                     }
+                    if is_doc {
+                        self.doc_comment(&text[1..]);
+                    }
+                } else if self.r#match('*') {
+                    self.block_comment();
                 } else {
                     self.add_token(TokenType::Slash)
                 }
@@ -111,9 +219,22 @@ impl<'a, 'p, T: Print> Scanner<'a, 'p, T> {
 
             '\"' => self.string(),
 
+            // Private member name, e.g. #balance
+            '#' if self.source_iter.peek().is_some_and(|&c| Self::is_alpha(c)) => {
+                while self.source_iter.peek().is_some_and(|&c| Self::is_alphanumeric(c)) {
+                    self.advance();
+                }
+                self.add_token(TokenType::PrivateIdentifier);
+            }
+
             //
             ' ' | '\t' | '\r' => (),
-            '\n' => self.line += 1,
+            '\n' => {
+                if self.optional_semicolons && self.line_ends_statement() {
+                    self.add_token(TokenType::Newline);
+                }
+                self.line += 1
+            }
 
             // 
             _ => {
@@ -133,6 +254,61 @@ impl<'a, 'p, T: Print> Scanner<'a, 'p, T> {
         }
     }
 
+    /// Adds text of `///` comment to the doc comment of the next token.
+    fn doc_comment(&mut self, text: &str) {
+        let text = text.strip_prefix(' ').unwrap_or(text);
+        match &mut self.doc {
+            Some(doc) => {
+                doc.push('\n');
+                doc.push_str(text);
+            }
+            None => self.doc = Some(text.to_string()),
+        }
+    }
+
+    /// Skips block comment after its opening '/*'. Block comments nest.
+    fn block_comment(&mut self) {
+        let mut depth = 1;
+        let mut ends_line = false;
+        while depth > 0 {
+            match self.source_iter.peek().copied() {
+                None => {
+                    self.err_reporter.error(
+                        self.line,
+                        self.start,
+                        self.current - self.start,
+                        "Unterminated block comment",
+                    );
+                    return;
+                }
+                Some('/') if self.peek_second() == Some('*') => {
+                    self.advance();
+                    self.advance();
+                    depth += 1;
+                }
+                Some('*') if self.peek_second() == Some('/') => {
+                    self.advance();
+                    self.advance();
+                    depth -= 1;
+                }
+                Some('\n') => {
+                    // Comment spanning lines ends the statement before it, as a line end would.
+                    if !ends_line && self.optional_semicolons && self.line_ends_statement() {
+                        ends_line = true;
+                    }
+                    self.advance();
+                    self.line += 1;
+                }
+                Some(_) => {
+                    self.advance();
+                }
+            }
+        }
+        if ends_line {
+            self.add_token(TokenType::Newline);
+        }
+    }
+
     fn number(&mut self) {
         while self.source_iter.peek().is_some_and(|&c| Self::is_digit(c)) {
             self.advance();
@@ -215,6 +391,13 @@ impl<'a, 'p, T: Print> Scanner<'a, 'p, T> {
             .next()
     }
 
+    /// Char after the one returned by peek() on source_iter.
+    fn peek_second(&self) -> Option<char> {
+        let mut ahead = self.source_iter.clone();
+        ahead.next();
+        ahead.next()
+    }
+
     fn advance(&mut self) -> char {
         self.current += 1;
         self.source_iter.next().unwrap()
@@ -230,11 +413,33 @@ impl<'a, 'p, T: Print> Scanner<'a, 'p, T> {
     }
 
     fn add_token(&mut self, token_type: TokenType) {
-        self.tokens.push(Rc::new(Token::new(
+        // Doc comment documents the declaration it's followed by, and is
+        // dropped before any other token. Line ends are skipped over.
+        let doc = match token_type {
+            TokenType::Newline => None,
+            TokenType::Fun | TokenType::Class | TokenType::Var | TokenType::Const | TokenType::Enum
+            | TokenType::Trait | TokenType::Macro | TokenType::At => self.doc.take(),
+            _ => {
+                self.doc = None;
+                None
+            }
+        };
+        match token_type {
+            TokenType::LeftParen | TokenType::LeftBracket | TokenType::LeftBrace => {
+                self.open_brackets.push(token_type.clone())
+            }
+            TokenType::RightParen | TokenType::RightBracket | TokenType::RightBrace => {
+                self.open_brackets.pop();
+            }
+            _ => (),
+        }
+        let mut token = Token::new(
             token_type,
             self.source[self.start..self.current].to_string(),
             self.line,
-        )));
+        );
+        token.doc = doc;
+        self.tokens.push(Rc::new(token));
     }
 }
 
@@ -275,21 +480,33 @@ mod tests {
 
     #[test]
     fn keywords() {
-        let source = "and class else false for fun if nil or return super this true var while";
+        let source = "and break class const do else enum false for fun if in is loop macro match nil or return static super this trait true typeof var while";
         let expected = vec![
             (TokenType::And, "and", 1),
+            (TokenType::Break, "break", 1),
             (TokenType::Class, "class", 1),
+            (TokenType::Const, "const", 1),
+            (TokenType::Do, "do", 1),
             (TokenType::Else, "else", 1),
+            (TokenType::Enum, "enum", 1),
             (TokenType::False, "false", 1),
             (TokenType::For, "for", 1),
             (TokenType::Fun, "fun", 1),
             (TokenType::If, "if", 1),
+            (TokenType::In, "in", 1),
+            (TokenType::Is, "is", 1),
+            (TokenType::Loop, "loop", 1),
+            (TokenType::Macro, "macro", 1),
+            (TokenType::Match, "match", 1),
             (TokenType::Nil, "nil", 1),
             (TokenType::Or, "or", 1),
             (TokenType::Return, "return", 1),
+            (TokenType::Static, "static", 1),
             (TokenType::Super, "super", 1),
             (TokenType::This, "this", 1),
+            (TokenType::Trait, "trait", 1),
             (TokenType::True, "true", 1),
+            (TokenType::Typeof, "typeof", 1),
             (TokenType::Var, "var", 1),
             (TokenType::While, "while", 1),
             (TokenType::Eof, "", 1)
@@ -313,7 +530,7 @@ mod tests {
         123.456
         .456
         123.";
-        let expected = vec![
+        let expected = [
             (TokenType::Number(123.0), "123", 1),
             (TokenType::Number(123.456), "123.456", 2),
             (TokenType::Dot, ".", 3),
@@ -339,7 +556,7 @@ mod tests {
     fn strings() {
         let source = "\"\"
         \"string\"";
-        let expected = vec![
+        let expected = [
             (TokenType::String("".to_string()), "\"\"", 1),
             (TokenType::String("string".to_string()), "\"string\"", 2),
             (TokenType::Eof, "", 2)
@@ -365,7 +582,7 @@ mod tests {
 
 
         end";
-        let expected = vec![
+        let expected = [
             (TokenType::Identifier, "space", 1),
             (TokenType::Identifier, "tabs", 1),
             (TokenType::Identifier, "newlines", 1),
@@ -384,4 +601,107 @@ mod tests {
             assert_eq!(tkn.line, expected[i].2);
         }
     }
+
+    #[test]
+    fn newlines_with_optional_semicolons() {
+        let source = "a = 1 +
+  2
+f(x,
+ y)
+
+
+b";
+        let expected = [
+            (TokenType::Identifier, "a", 1),
+            (TokenType::Equal, "=", 1),
+            (TokenType::Number(1.), "1", 1),
+            (TokenType::Plus, "+", 1),
+            (TokenType::Number(2.), "2", 2),
+            (TokenType::Newline, "\n", 2),
+            (TokenType::Identifier, "f", 3),
+            (TokenType::LeftParen, "(", 3),
+            (TokenType::Identifier, "x", 3),
+            (TokenType::Comma, ",", 3),
+            (TokenType::Identifier, "y", 4),
+            (TokenType::RightParen, ")", 4),
+            (TokenType::Newline, "\n", 4),
+            (TokenType::Identifier, "b", 7),
+            (TokenType::Newline, "", 7),
+            (TokenType::Eof, "", 7)
+        ]; // (tken_type, lexeme, line)
+        let printer = TestPrinter::default();
+        let error_reporter = ErrorReporter::new(
+            source, false, &printer
+        );
+        let mut scanner = Scanner::new(source,  &error_reporter).with_optional_semicolons(true);
+        scanner.scan_tokens();
+        assert_eq!(scanner.tokens.len(), expected.len());
+        for (i, tkn) in scanner.tokens.iter().enumerate() {
+            assert_eq!(tkn.token_type, expected[i].0);
+            assert_eq!(tkn.lexeme, expected[i].1);
+            assert_eq!(tkn.line, expected[i].2);
+        }
+    }
+
+    #[test]
+    fn block_comments() {
+        let source = "a /* one
+        /* nested
+        */ still comment */ b
+        /* unterminated /* */";
+        let expected = [
+            (TokenType::Identifier, "a", 1),
+            (TokenType::Identifier, "b", 3),
+            (TokenType::Eof, "", 4)
+        ]; // (tken_type, lexeme, line)
+        let printer = TestPrinter::default();
+        let error_reporter = ErrorReporter::new(
+            source, false, &printer
+        );
+        let mut scanner = Scanner::new(source,  &error_reporter);
+        scanner.scan_tokens();
+        assert_eq!(scanner.tokens.len(), expected.len());
+        for (i, tkn) in scanner.tokens.iter().enumerate() {
+            assert_eq!(tkn.token_type, expected[i].0);
+            assert_eq!(tkn.lexeme, expected[i].1);
+            assert_eq!(tkn.line, expected[i].2);
+        }
+        assert!(printer.result.borrow()[0].starts_with("Error: Unterminated block comment"));
+    }
+
+    #[test]
+    fn doc_comments() {
+        let source = "/// Adds two numbers.
+        ///   Returns their sum.
+        fun add // not a doc comment
+        //// not a doc comment either
+        x";
+        let printer = TestPrinter::default();
+        let error_reporter = ErrorReporter::new(
+            source, false, &printer
+        );
+        let mut scanner = Scanner::new(source,  &error_reporter);
+        scanner.scan_tokens();
+        assert_eq!(scanner.tokens[0].token_type, TokenType::Fun);
+        assert_eq!(scanner.tokens[0].doc.as_deref(), Some("Adds two numbers.\n  Returns their sum."));
+        assert_eq!(scanner.tokens[1].doc, None);
+        assert_eq!(scanner.tokens[2].doc, None);
+    }
+
+    #[test]
+    fn doc_comment_only_before_declaration() {
+        let source = "/// Not documenting a declaration.
+        print 1;
+        var x;";
+        let printer = TestPrinter::default();
+        let error_reporter = ErrorReporter::new(
+            source, false, &printer
+        );
+        let mut scanner = Scanner::new(source,  &error_reporter);
+        scanner.scan_tokens();
+        assert_eq!(scanner.tokens[0].token_type, TokenType::Print);
+        assert_eq!(scanner.tokens[0].doc, None);
+        assert_eq!(scanner.tokens[3].token_type, TokenType::Var);
+        assert_eq!(scanner.tokens[3].doc, None);
+    }
 }
@@ -1,25 +1,138 @@
 use std::rc::Rc;
 
-use super::{expr::Expr, token::Token};
+use super::{expr::Expr, pattern::Pattern, token::Token};
+
+#[derive(Debug, Clone)]
+pub struct EnumVariant {
+    pub name: Rc<Token>,
+    /// Names of payload fields, None for variants without payload.
+    pub fields: Option<Vec<Rc<Token>>>
+}
+
+/// Class-level field declared with `static var`.
+#[derive(Debug, Clone)]
+pub struct StaticField {
+    pub name: Rc<Token>,
+    pub initializer: Option<Expr>
+}
+
+#[derive(Debug, Clone)]
+pub struct ClassDeclaration {
+    pub name: Rc<Token>,
+    /// Traits composed into the class with `with`.
+    pub traits: Vec<Expr>,
+    pub methods: Vec<Stmt>,
+    pub getters: Vec<Stmt>,
+    pub setters: Vec<Stmt>,
+    pub class_methods: Vec<Stmt>,
+    pub static_fields: Vec<StaticField>
+}
+
+/// `macro name(params) { body }`, expanded at every call before resolving.
+/// Body is a block expression.
+#[derive(Debug, Clone)]
+pub struct MacroDeclaration {
+    pub name: Rc<Token>,
+    pub params: Vec<Rc<Token>>,
+    pub body: Expr
+}
+
+/// Method declared in a trait without body, which classes must provide.
+#[derive(Debug, Clone)]
+pub struct RequiredMethod {
+    pub name: Rc<Token>,
+    pub params: Vec<Rc<Token>>
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<Expr>,
+    pub body: Stmt
+}
+
+/// `@expression` in front of function or method declaration. Value of
+/// expression is called with the function and returns its replacement.
+#[derive(Debug, Clone)]
+pub struct Decorator {
+    pub at: Rc<Token>,
+    pub expression: Expr
+}
+
+/// Type written after ':' in declarations, as in `List<Number>`, or
+/// `String?` for values which may also be nil.
+#[derive(Debug, Clone)]
+pub struct TypeAnnotation {
+    pub name: Rc<Token>,
+    pub arguments: Vec<TypeAnnotation>,
+    pub optional: bool
+}
+
+/// Annotated types of function parameters and return value, None where
+/// not annotated.
+#[derive(Debug, Clone, Default)]
+pub struct Signature {
+    pub params: Vec<Option<TypeAnnotation>>,
+    pub return_type: Option<TypeAnnotation>
+}
 
 #[derive(Debug, Clone)]
 pub enum Stmt {
     /// Expression( expr )
     Expression(Expr),
-    /// Function
-    Function { name: Rc<Token>, params: Vec<Rc<Token>>, body: Vec<Stmt>},
+    /// Function, with decorators listed in source order.
+    Function {
+        name: Rc<Token>,
+        params: Vec<Rc<Token>>,
+        body: Vec<Stmt>,
+        decorators: Vec<Decorator>,
+        signature: Signature
+    },
     /// If( condition, then_statmenet, else_statement )
     If(Expr, Box<Stmt>, Box<Option<Stmt>>),
     /// Print( expr )
     Print(Expr),
-    /// Var( var_name, initializer )
-    Var(Rc<Token>, Option<Expr>),
+    /// Var( pattern, annotation, initializer )
+    Var(Pattern, Option<TypeAnnotation>, Option<Expr>),
+    /// Const( pattern, annotation, initializer )
+    Const(Pattern, Option<TypeAnnotation>, Expr),
     /// Block( statements )
     Block(Vec<Stmt>),
-    /// While( condition, body )
-    While(Expr, Box<Stmt>),
+    /// Loop running body while condition holds. do_while loops run body
+    /// once before checking condition first time.
+    While { label: Option<Rc<Token>>, condition: Expr, body: Box<Stmt>, do_while: bool },
+    /// Break out of loop with given label, innermost loop if None.
+    Break { keyword: Rc<Token>, label: Option<Rc<Token>> },
+    /// Match( keyword, subject, arms )
+    Match { keyword: Rc<Token>, subject: Expr, arms: Vec<MatchArm> },
 
     Return { return_keyword: Rc<Token>, expression: Option<Expr> },
     
-    Class { name: Rc<Token>, methods: Vec<Stmt> }
-}
\ No newline at end of file
+    Class(ClassDeclaration),
+
+    Trait { name: Rc<Token>, methods: Vec<Stmt>, required: Vec<RequiredMethod> },
+
+    /// Extend( name, class, methods ): adds methods to class, or to built-in
+    /// type if name is one of builtins::TYPE_NAMES. class is variable expression of name.
+    Extend { name: Rc<Token>, class: Expr, methods: Vec<Stmt> },
+
+    Enum { name: Rc<Token>, variants: Vec<EnumVariant> },
+
+    /// Macro declaration, only at top level. Removed from the program by
+    /// macros::MacroExpander before resolving.
+    Macro(MacroDeclaration)
+}
+
+impl Stmt {
+    /// Whether statement always leaves the function or loop running it.
+    pub fn diverges(&self) -> bool {
+        match self {
+            Stmt::Return { .. } | Stmt::Break { .. } => true,
+            Stmt::Block(stmts) => stmts.last().is_some_and(Stmt::diverges),
+            Stmt::If(_, then_stmt, else_stmt) => {
+                then_stmt.diverges() && else_stmt.as_ref().as_ref().is_some_and(Stmt::diverges)
+            }
+            _ => false,
+        }
+    }
+}
@@ -5,7 +5,9 @@ pub struct Token {
     pub token_type: TokenType,
     // pub lexeme: &'a str,
     pub lexeme: String,
-    pub line: usize
+    pub line: usize,
+    /// Text of `///` doc comment lines right before the token.
+    pub doc: Option<String>
 }
 
 impl Token {
@@ -16,6 +18,7 @@ impl Token {
             token_type,
             lexeme,
             line,
+            doc: None,
         }
     }
 }
\ No newline at end of file
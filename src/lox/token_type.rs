@@ -6,13 +6,19 @@ pub enum TokenType {
     RightParen,
     LeftBrace,
     RightBrace,
+    LeftBracket,
+    RightBracket,
     Comma,
+    Colon,
     Dot,
     Minus,
     Plus,
     SemiColon,
     Slash,
     Star,
+    Pipe,
+    At,
+    Question,
 
     // One or two character tokens.
     Bang,
@@ -21,31 +27,49 @@ pub enum TokenType {
     EqualEqual,
     Greater,
     GreaterEqual,
+    PipeGreater,
     Less,
     LessEqual,
+    FatArrow,
+    Arrow,
 
     // Literals.
     Identifier,
+    PrivateIdentifier,
     String(String),
     Number(f64),
 
     // Keywords.
     And,
+    Break,
     Class,
+    Const,
+    Do,
     Else,
+    Enum,
     False,
     Fun,
     For,
     If,
+    In,
+    Is,
+    Loop,
+    Macro,
+    Match,
     Nil,
     Or,
     Print,
     Return,
+    Static,
     Super,
     This,
+    Trait,
     True,
+    Typeof,
     Var,
     While,
 
+    /// End of line ending a statement, only with optional semicolons.
+    Newline,
     Eof,
 }
@@ -0,0 +1,47 @@
+use std::{cell::Cell, collections::HashMap, rc::Rc};
+
+use super::{callable::ForeignFn, stmt::RequiredMethod, token::Token};
+
+thread_local!{ 
+    pub static TRAIT_ID: Cell<usize> = const { Cell::new(1) };
+}
+
+/// Reusable set of methods composed into classes with `with`.
+#[derive(Debug)]
+pub struct Trait {
+    id: usize,
+    pub name: Rc<Token>,
+    pub methods: HashMap<String, ForeignFn>,
+    /// Methods declared without body, which classes using the trait must provide.
+    pub required: Vec<RequiredMethod>
+}
+
+impl Trait {
+    pub fn new(
+        name: Rc<Token>,
+        methods: HashMap<String, ForeignFn>,
+        required: Vec<RequiredMethod>
+    ) -> Self {
+        Self { id: Self::get_inc_id(), name, methods, required }
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    fn get_inc_id() -> usize {
+        // Assign new incrementing id to every new trait.
+        let mut id: usize = 0;
+        TRAIT_ID.with(|trait_id| {
+            id = trait_id.get();
+            trait_id.set(id + 1);
+        });
+        id
+    }
+}
+
+impl PartialEq for Trait {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
@@ -1,33 +1,39 @@
-mod lox;
-// mod printer;
-
 use std::{env, process, io::{self, Write}, fs};
 
-use lox::Lox;
+use rlox::lox::{self, Lox};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
     dbg!(&args);
+    let optional_semicolons = args.iter().any(|arg| arg == "--optional-semicolons");
+    let strict = args.iter().any(|arg| arg == "--strict");
+    let args: Vec<&String> = args.iter()
+        .filter(|arg| *arg != "--optional-semicolons" && *arg != "--strict")
+        .collect();
     #[allow(clippy::comparison_chain)]
     if args.len() > 2 {
-        println!("Usage: jlox-rs [script]");
+        println!("Usage: jlox-rs [--optional-semicolons] [--strict] [script]");
         process::exit(64); // 64: The command was used incorrectly
     } else if args.len() == 2 {
-        run_file(&args[1]);
+        run_file(args[1], optional_semicolons, strict);
     } else {
-        run_prompt();
+        run_prompt(optional_semicolons, strict);
     }
 }
 
-fn run_file(file_path: &str) {
+fn run_file(file_path: &str, optional_semicolons: bool, strict: bool) {
     let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
     let mut lox_runner = Lox::new(false, &lox::printer::CliPrinter);
+    lox_runner.set_optional_semicolons(optional_semicolons);
+    lox_runner.set_strict(strict);
     let exit_code = lox_runner.run(&source_code);
     process::exit(exit_code)
 }
 
-fn run_prompt() {
+fn run_prompt(optional_semicolons: bool, strict: bool) {
     let mut lox_runner = Lox::new(true, &lox::printer::CliPrinter);
+    lox_runner.set_optional_semicolons(optional_semicolons);
+    lox_runner.set_strict(strict);
     let stdin = io::stdin();
     loop {
         let mut input = String::new();
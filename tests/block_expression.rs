@@ -0,0 +1,101 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn if_expression() {
+    let file_path = "./tests/block_expression/if_expression.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "1", "no", "negative", "zero", "positive", "Nil"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn block() {
+    let file_path = "./tests/block_expression/block.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "9", "computing", "3", "3"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn map_literal() {
+    let file_path = "./tests/block_expression/map_literal.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "{}", "{\"a\": 1, \"b\": 2}", "{\"k\": \"k\"}"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn scope() {
+    let file_path = "./tests/block_expression/scope.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "inner", "outer", "2"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn no_value() {
+    let file_path = "./tests/block_expression/no_value.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "side effect", "Nil"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn return_in_nested_function() {
+    let file_path = "./tests/block_expression/return_in_nested_function.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "from inner"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn return_inside() {
+    let file_path = "./tests/block_expression/return_inside.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can't return from inside a block expression"));
+}
+
+#[test]
+fn missing_semicolon() {
+    let file_path = "./tests/block_expression/missing_semicolon.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Expect ';' after value"));
+}
@@ -0,0 +1,207 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn class_method() {
+    let file_path = "./tests/class/class_method.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "9"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn static_field() {
+    let file_path = "./tests/class/static_field.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "2", "Nil"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn this_in_class_method() {
+    let file_path = "./tests/class/this_in_class_method.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "b"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn static_initializer_uses_class() {
+    let file_path = "./tests/class/static_initializer_uses_class.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "0"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn class_method_not_on_instance() {
+    let file_path = "./tests/class/class_method_not_on_instance.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Undefined property 'square'"));
+}
+
+#[test]
+fn undefined_static() {
+    let file_path = "./tests/class/undefined_static.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Undefined static property 'pi' of class 'Math'"));
+}
+
+#[test]
+fn init_arguments() {
+    let file_path = "./tests/class/init_arguments.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "3", "true",
+        "Error: Expected 2 arguments, received 1.\n   line 15 | Point(1); // expect runtime error: Expected 2 arguments, received 1."
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn this_outside_class() {
+    let file_path = "./tests/class/this_outside_class.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can't use 'this' outside of a class"));
+}
+
+#[test]
+fn return_value_from_init() {
+    let file_path = "./tests/class/return_value_from_init.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can't return a value from an initializer"));
+}
+
+#[test]
+fn getter() {
+    let file_path = "./tests/class/getter.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "6", "12"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn setter() {
+    let file_path = "./tests/class/setter.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "212", "100", "212"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn field_shadows_getter() {
+    let file_path = "./tests/class/field_shadows_getter.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "getter", "field"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn call_getter() {
+    let file_path = "./tests/class/call_getter.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can't call getter 'area' of class 'Rect', access it without '()'"));
+}
+
+#[test]
+fn getter_returns_function() {
+    let file_path = "./tests/class/getter_returns_function.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "hello lox"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn setter_arity() {
+    let file_path = "./tests/class/setter_arity.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Setter must take exactly one parameter"));
+}
+
+#[test]
+fn destructure_getter() {
+    let file_path = "./tests/class/destructure_getter.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "10"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn method_named_set() {
+    let file_path = "./tests/class/method_named_set.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "1"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
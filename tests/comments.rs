@@ -0,0 +1,65 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn nested() {
+    let file_path = "./tests/comments/nested.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "1", "2", "3"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn line_numbers() {
+    let file_path = "./tests/comments/line_numbers.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Undefined variable 'undefined'.\n   line 6 | print undefined;"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn unterminated() {
+    let file_path = "./tests/comments/unterminated.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Unterminated block comment"));
+}
+
+#[test]
+fn doc() {
+    let file_path = "./tests/comments/doc.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "3"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn optional_semicolons() {
+    let file_path = "./tests/comments/optional_semicolons.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "1", "2"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
@@ -0,0 +1,92 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn read() {
+    let file_path = "./tests/const/read.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "hello", "3", "reassigned"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn assign_local() {
+    let file_path = "./tests/const/assign_local.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Can't assign to constant 'limit'.\n   line 3 |   limit = 20; // Error: Can't assign to constant 'limit'.", "Note: 'limit' is declared constant here.\n   line 2 |   const limit = 10;"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn assign_local_pattern() {
+    let file_path = "./tests/const/assign_local_pattern.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can't assign to constant 'b'."));
+}
+
+#[test]
+fn assign_global() {
+    let file_path = "./tests/const/assign_global.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "10", "Error: Can't assign to constant 'limit'.\n   line 8 | raise();", "Note: 'limit' is declared constant here.\n   line 1 | const limit = 10;"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn redefine_global() {
+    let file_path = "./tests/const/redefine_global.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Can't redeclare constant 'a'.\n   line 2 | var a = 2;",
+        "Note: 'a' is declared constant here.\n   line 1 | const a = 1;"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn missing_initializer() {
+    let file_path = "./tests/const/missing_initializer.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Expected initializer for constant."));
+}
+
+#[test]
+fn redefine_global_of_earlier_run() {
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(true, &printer);
+    lox_runner.run("const a = 1;");
+    lox_runner.run("var a = 2;");
+    lox_runner.run("fun a() {}");
+    lox_runner.run("print a;");
+    let result = vec![
+        "Error: Can't redeclare constant 'a'.", "Note: 'a' is declared constant here.",
+        "Error: Can't redeclare constant 'a'.", "Note: 'a' is declared constant here.",
+        "1"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
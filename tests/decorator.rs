@@ -0,0 +1,176 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn memoize() {
+    let file_path = "./tests/decorator/memoize.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "832040", "31"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn stacked() {
+    let file_path = "./tests/decorator/stacked.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "HELLO LOX!"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn arguments() {
+    let file_path = "./tests/decorator/arguments.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "attempt 1", "attempt 2", "attempt 3", "ok"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn method() {
+    let file_path = "./tests/decorator/method.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "calling with 2", "calling with 3", "5", "calling with 1", "1"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn method_once() {
+    let file_path = "./tests/decorator/method_once.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "value", "1", "1"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn replacement() {
+    let file_path = "./tests/decorator/replacement.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "42"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn not_callable() {
+    let file_path = "./tests/decorator/not_callable.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can only call functions and classes"));
+}
+
+#[test]
+fn not_function() {
+    let file_path = "./tests/decorator/not_function.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Expected function declaration after decorators"));
+}
+
+#[test]
+fn class_method() {
+    let file_path = "./tests/decorator/class_method.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Only methods can be decorated"));
+}
+
+#[test]
+fn initializer() {
+    let file_path = "./tests/decorator/initializer.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Initializer can't be decorated.\n   line 5 |     init(x) { this.x = x; }"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn getter() {
+    let file_path = "./tests/decorator/getter.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Only methods can be decorated.\n   line 5 |     area { return 3; }"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn setter() {
+    let file_path = "./tests/decorator/setter.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Only methods can be decorated.\n   line 5 |     set radius(value) { this.r = value; }"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn method_not_callable() {
+    let file_path = "./tests/decorator/method_not_callable.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "before", "Error: Can only call functions and classes.\n   line 3 |     @1"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn method_other_instance() {
+    let file_path = "./tests/decorator/method_other_instance.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "a/b", "a"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
@@ -0,0 +1,105 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn list_declaration() {
+    let file_path = "./tests/destructuring/list_declaration.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "a", "b", "6"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn field_declaration() {
+    let file_path = "./tests/destructuring/field_declaration.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "2", "3"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn swap() {
+    let file_path = "./tests/destructuring/swap.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "b", "a", "2", "1"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn missing_value() {
+    let file_path = "./tests/destructuring/missing_value.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: No value to destructure into 'c' of '[a, b, c]'."));
+}
+
+#[test]
+fn too_many_values() {
+    let file_path = "./tests/destructuring/too_many_values.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Too many values to destructure into '[a, b]', expected 2 received 3."));
+}
+
+#[test]
+fn missing_field() {
+    let file_path = "./tests/destructuring/missing_field.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Undefined property 'y' to destructure into '{x, y}'."));
+}
+
+#[test]
+fn missing_initializer() {
+    let file_path = "./tests/destructuring/missing_initializer.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Expected initializer for destructuring declaration."));
+}
+
+#[test]
+fn duplicate_binding() {
+    let file_path = "./tests/destructuring/duplicate_binding.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Already a variable with this name in this scope."));
+}
+
+#[test]
+fn map_keys() {
+    let file_path = "./tests/destructuring/map_keys.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "localhost", "8080"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
@@ -0,0 +1,95 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn variants() {
+    let file_path = "./tests/enum/variants.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Color.Red", "Color.Blue(\"#00f\")", "#00f", "Color.Green", "<enum Color>"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn identity() {
+    let file_path = "./tests/enum/identity.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "true", "false", "false", "true", "false"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn match_variants() {
+    let file_path = "./tests/enum/match_variants.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "12", "square", "6", "0"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn local_enum() {
+    let file_path = "./tests/enum/local_enum.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Answer.Yes"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn undefined_variant() {
+    let file_path = "./tests/enum/undefined_variant.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Undefined variant 'Purple' of enum 'Color'."));
+}
+
+#[test]
+fn duplicate_variant() {
+    let file_path = "./tests/enum/duplicate_variant.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Already a variant with this name in this enum."));
+}
+
+#[test]
+fn variant_not_global() {
+    let file_path = "./tests/enum/variant_not_global.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Undefined variable 'Red'."));
+}
+
+#[test]
+fn payload_arity() {
+    let file_path = "./tests/enum/payload_arity.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Expected 1 arguments, received 2.\n   line 2 | Color.Blue(1, 2);"));
+}
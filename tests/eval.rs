@@ -0,0 +1,146 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn last_value() {
+    let file_path = "./tests/eval/last_value.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "3", "20", "10", "7", "Nil", "Nil"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn globals() {
+    let file_path = "./tests/eval/globals.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "100", "2", "15"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn syntax_error() {
+    let file_path = "./tests/eval/syntax_error.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "before", "Error: Invalid source at line 1: Expected variable name.\n   line 2 | eval(\"var = 1;\");"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn resolve_error() {
+    let file_path = "./tests/eval/resolve_error.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Invalid source at line 1: Can't return from top-level code.\n   line 1 | eval(\"return 1;\");"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn runtime_error() {
+    let file_path = "./tests/eval/runtime_error.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Both Operands must be either number or string..\n   line 1 | eval(\"1 + nil;\");"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn not_string() {
+    let file_path = "./tests/eval/not_string.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Argument of eval() must be a string.\n   line 1 | eval(42);"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn parse_tree() {
+    let file_path = "./tests/eval/parse_tree.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "1", "Print", "Binary", "+", "1", "x"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn parse_declarations() {
+    let file_path = "./tests/eval/parse_declarations.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "add", "[\"a\", \"b\"]", "Return", "ListPattern", "y"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn parse_error() {
+    let file_path = "./tests/eval/parse_error.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Invalid source at line 1: Expected Expression.\n   line 1 | print parse(\"1 +\");"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn macro_eval() {
+    let file_path = "./tests/eval/macro_eval.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "42", "Macro"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn macro_across_calls() {
+    let file_path = "./tests/eval/macro_across_calls.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "12", "2"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
@@ -0,0 +1,169 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn string() {
+    let file_path = "./tests/extend/string.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "HELLO!", "HI!"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn class() {
+    let file_path = "./tests/extend/class.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "3", "(1, 2)"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn natives() {
+    let file_path = "./tests/extend/natives.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "3", "3", "true", "lox", "true", "[\"a\", \"b\"]", "false", "2.5", "3"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn list_and_number() {
+    let file_path = "./tests/extend/list_and_number.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "6", "12"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn bound_method() {
+    let file_path = "./tests/extend/bound_method.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "abab", "AB"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn contextual() {
+    let file_path = "./tests/extend/contextual.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "still a name"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn existing_method() {
+    let file_path = "./tests/extend/existing_method.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Type 'String' already has method 'upper'"));
+}
+
+#[test]
+fn existing_class_method() {
+    let file_path = "./tests/extend/existing_class_method.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Class 'Greeter' already has method 'greet'"));
+}
+
+#[test]
+fn undefined_method() {
+    let file_path = "./tests/extend/undefined_method.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Undefined method 'reverse' of type 'String'"));
+}
+
+#[test]
+fn not_class() {
+    let file_path = "./tests/extend/not_class.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can only extend classes and built-in types, 'Thing' is not a class"));
+}
+
+#[test]
+fn private() {
+    let file_path = "./tests/extend/private.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can't access private member '#value' in extension methods"));
+}
+
+#[test]
+fn failed_class_extension_adds_nothing() {
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(true, &printer);
+    lox_runner.run("class Greeter { greet() { return \"hi\"; } }");
+    lox_runner.run("extend Greeter { wave() { return \"wave\"; } greet() { return \"hello\"; } }");
+    lox_runner.run("print Greeter().wave();");
+    let result = vec![
+        "Error: Class 'Greeter' already has method 'greet'.", "Error: Undefined property 'wave'."
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn failed_type_extension_adds_nothing() {
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(true, &printer);
+    lox_runner.run("extend String { shout() { return this + \"!\"; } upper() { return this; } }");
+    lox_runner.run("print \"hi\".shout();");
+    let result = vec![
+        "Error: Type 'String' already has method 'upper'.", "Error: Undefined method 'shout' of type 'String'."
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn duplicate_method() {
+    let file_path = "./tests/extend/duplicate_method.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Already a method with this name in this extension.\n   line 5 |     twice() {"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
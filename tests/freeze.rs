@@ -0,0 +1,98 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn instance() {
+    let file_path = "./tests/freeze/instance.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert_eq!(printer.result.borrow()[0], "plugin");
+    assert!(printer.result.borrow()[1].starts_with("Error: Can't set property 'name' on frozen instance of 'Config'"));
+}
+
+#[test]
+fn list() {
+    let file_path = "./tests/freeze/list.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert_eq!(printer.result.borrow()[0], "1");
+    assert!(printer.result.borrow()[1].starts_with("Error: Can't modify frozen list"));
+}
+
+#[test]
+fn map() {
+    let file_path = "./tests/freeze/map.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert_eq!(printer.result.borrow()[0], "false");
+    assert!(printer.result.borrow()[1].starts_with("Error: Can't modify frozen map"));
+}
+
+#[test]
+fn shallow() {
+    let file_path = "./tests/freeze/shallow.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "b", "true", "false"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn deep() {
+    let file_path = "./tests/freeze/deep.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "true", "true", "true", "true", "true"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn deep_write() {
+    let file_path = "./tests/freeze/deep_write.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can't modify frozen list"));
+}
+
+#[test]
+fn cycle() {
+    let file_path = "./tests/freeze/cycle.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "true", "true"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn copy_is_not_frozen() {
+    let file_path = "./tests/freeze/copy_is_not_frozen.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "3", "1"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
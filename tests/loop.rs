@@ -0,0 +1,167 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn do_while() {
+    let file_path = "./tests/loop/do_while.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "0", "1", "2", "once"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn infinite() {
+    let file_path = "./tests/loop/infinite.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "3"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn unlabeled_break() {
+    let file_path = "./tests/loop/unlabeled_break.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "0", "1", "while", "while"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn labeled() {
+    let file_path = "./tests/loop/labeled.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "[1, 2]", "2", "done"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn return_from_loop() {
+    let file_path = "./tests/loop/return_from_loop.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "2"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn block_expression() {
+    let file_path = "./tests/loop/block_expression.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "1", "2"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn optional_semicolons() {
+    let file_path = "./tests/loop/optional_semicolons.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "3"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn break_outside_loop() {
+    let file_path = "./tests/loop/break_outside_loop.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can't break outside of a loop"));
+}
+
+#[test]
+fn break_in_closure() {
+    let file_path = "./tests/loop/break_in_closure.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can't break outside of a loop"));
+}
+
+#[test]
+fn break_block_expression() {
+    let file_path = "./tests/loop/break_block_expression.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can't break out of a block expression"));
+}
+
+#[test]
+fn unknown_label() {
+    let file_path = "./tests/loop/unknown_label.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Undefined label 'inner'"));
+}
+
+#[test]
+fn shadowed_label() {
+    let file_path = "./tests/loop/shadowed_label.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Label 'outer' shadows label of enclosing loop"));
+}
+
+#[test]
+fn label_without_loop() {
+    let file_path = "./tests/loop/label_without_loop.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Expected loop after label 'outer'"));
+}
+
+#[test]
+fn break_from_match() {
+    let file_path = "./tests/loop/break_from_match.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "[(0, 0), 0, (1, 0), 1, (2, 0)]"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
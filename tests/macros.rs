@@ -0,0 +1,173 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn unless() {
+    let file_path = "./tests/macros/unless.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "small"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn value() {
+    let file_path = "./tests/macros/value.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "7", "11"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn hygiene() {
+    let file_path = "./tests/macros/hygiene.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "2", "1"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn lazy_arguments() {
+    let file_path = "./tests/macros/lazy_arguments.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "2", "hi", "hi"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn before_declaration() {
+    let file_path = "./tests/macros/before_declaration.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "not positive"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn nested() {
+    let file_path = "./tests/macros/nested.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "small"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn loop_counter() {
+    let file_path = "./tests/macros/loop_counter.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "10", "11", "12"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn arity() {
+    let file_path = "./tests/macros/arity.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Macro 'unless' expects 2 arguments, received 1.\n   line 4 | unless(true);", "Note: Macro 'unless' is declared here.\n   line 1 | macro unless(cond, body) {"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn recursive() {
+    let file_path = "./tests/macros/recursive.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Macro 'forever' expands into itself.\n   line 3 |     forever(body);", "Note: Macro 'forever' is declared here.\n   line 1 | macro forever(body) {"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn assign_argument() {
+    let file_path = "./tests/macros/assign_argument.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Can't assign to argument 'target' of macro 'reset'.\n   line 7 | reset(1 + 2);", "Note: Macro 'reset' is declared here.\n   line 1 | macro reset(target) {", "Note: Argument is used here.\n   line 2 |     target = 0;"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn as_value() {
+    let file_path = "./tests/macros/as_value.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Macro 'unless' can only be called.\n   line 4 | var f = unless;"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn not_top_level() {
+    let file_path = "./tests/macros/not_top_level.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Macros can only be declared at top level.\n   line 2 |     macro inner(x) { x }"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn macros_of_earlier_runs() {
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(true, &printer);
+    lox_runner.run("macro twice(e) { e + e }");
+    lox_runner.run("print twice(2);");
+    lox_runner.run("macro twice(e) { e * 2 }");
+    lox_runner.run("print twice(5);");
+    let result = vec![
+        "4", "10"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
@@ -0,0 +1,75 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn literals() {
+    let file_path = "./tests/match/literals.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "small", "small", "negative one", "string", "yes", "nothing", "other"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn list_and_tuple() {
+    let file_path = "./tests/match/list_and_tuple.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "empty list", "one: x", "abc", "ab", "tuple 3", "unknown"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn instance() {
+    let file_path = "./tests/match/instance.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "first quadrant", "3", "right", "elsewhere", "has width", "not a point"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn scope() {
+    let file_path = "./tests/match/scope.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "inner", "outer"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn non_exhaustive() {
+    let file_path = "./tests/match/non_exhaustive.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: No match arm for value (1, \"x\")."));
+}
+
+#[test]
+fn alternatives_bindings() {
+    let file_path = "./tests/match/alternatives_bindings.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: All alternatives of a pattern must bind the same variables."));
+}
@@ -0,0 +1,137 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn uninitialized() {
+    let file_path = "./tests/nil_safety/uninitialized.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Warning: 'a' is nil as operand of '+'.\n   line 3 | print a + 1;", "before", "Error: Both Operands must be either number or string..\n   line 3 | print a + 1;"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn narrowing() {
+    let file_path = "./tests/nil_safety/narrowing.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "3", "4"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn early_return() {
+    let file_path = "./tests/nil_safety/early_return.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "8", "0"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn optional_param() {
+    let file_path = "./tests/nil_safety/optional_param.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Warning: 'x' may be nil as operand of '*'.\n   line 2 |     return x * 2;", "8"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn call() {
+    let file_path = "./tests/nil_safety/call.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Warning: 'f' is nil as called value."));
+}
+
+#[test]
+fn property() {
+    let file_path = "./tests/nil_safety/property.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Warning: 'p' may be nil as object of property 'x'."));
+}
+
+#[test]
+fn logical() {
+    let file_path = "./tests/nil_safety/logical.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "false", "true"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn assigned() {
+    let file_path = "./tests/nil_safety/assigned.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "4", "2"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn captured() {
+    let file_path = "./tests/nil_safety/captured.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Operand must be number."));
+}
+
+#[test]
+fn do_while() {
+    let file_path = "./tests/nil_safety/do_while.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Warning: 'x' is nil as object of property 'name'.\n   line 3 |     print x.name;", "Error: Only instances have property.\n   line 3 |     print x.name;"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn loop_exit() {
+    let file_path = "./tests/nil_safety/loop_exit.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Warning: 'c' may be nil as operand of '+'.\n   line 18 | print c + 1;", "2", "3", "4"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
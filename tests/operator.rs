@@ -0,0 +1,88 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn vector() {
+    let file_path = "./tests/operator/vector.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "4", "6", "6", "-10"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn equality() {
+    let file_path = "./tests/operator/equality.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "true", "false", "true", "true", "false"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn comparison() {
+    let file_path = "./tests/operator/comparison.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "true", "false", "false", "true"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn reflected() {
+    let file_path = "./tests/operator/reflected.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "3"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn left_before_reflected() {
+    let file_path = "./tests/operator/left_before_reflected.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "A.__add__", "B.__radd__"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn missing_method() {
+    let file_path = "./tests/operator/missing_method.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Both Operands must be either number or string"));
+}
+
+#[test]
+fn missing_neg() {
+    let file_path = "./tests/operator/missing_neg.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Operand must be number"));
+}
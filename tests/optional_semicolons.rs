@@ -0,0 +1,131 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn pragma() {
+    let file_path = "./tests/optional_semicolons/pragma.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "3", "3"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn continuation() {
+    let file_path = "./tests/optional_semicolons/continuation.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "6", "[1, 2, 3]", "3"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn new_statement() {
+    let file_path = "./tests/optional_semicolons/new_statement.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "1"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn blocks() {
+    let file_path = "./tests/optional_semicolons/blocks.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "else", "{\"a\": 1, \"b\": 2}", "9", "8"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn semicolons_still_work() {
+    let file_path = "./tests/optional_semicolons/semicolons_still_work.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "3", "Nil"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn method_chain() {
+    let file_path = "./tests/optional_semicolons/method_chain.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "LOX", "4"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn without_pragma() {
+    let file_path = "./tests/optional_semicolons/without_pragma.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Expected ';' after variable declaration"));
+}
+
+#[test]
+fn flag() {
+    let file_path = "./tests/optional_semicolons/flag.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.set_optional_semicolons(true);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "hello"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn brace_on_next_line() {
+    let file_path = "./tests/optional_semicolons/brace_on_next_line.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "3", "7"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn braceless_body() {
+    let file_path = "./tests/optional_semicolons/braceless_body.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "ready", "3", "0", "1", "1"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
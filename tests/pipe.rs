@@ -0,0 +1,52 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn chain() {
+    let file_path = "./tests/pipe/chain.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "[1, 4, 9, 16, 25]", "35", "35"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn precedence() {
+    let file_path = "./tests/pipe/precedence.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "6", "14", "lox"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn method() {
+    let file_path = "./tests/pipe/method.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "hello lox!"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn not_call() {
+    let file_path = "./tests/pipe/not_call.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Expected function call after '|>', as in 'value |> f()'"));
+}
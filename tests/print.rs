@@ -0,0 +1,94 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn instance_fields() {
+    let file_path = "./tests/print/instance_fields.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "<instance Point{x: 1, y: \"a\"}>", "<instance Point{x: 1, y: 2}>"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn names() {
+    let file_path = "./tests/print/names.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "<fn add()>", "<class Point>", "<fn norm()>", "<native-fn clock()>"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn collections() {
+    let file_path = "./tests/print/collections.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "[1, \"two\", [3], Nil]", "{\"a\": 1, 2: true}", "(\"x\", [1])", "top level strings are not quoted"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn user_to_string() {
+    let file_path = "./tests/print/user_to_string.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "$5", "$7", "[$1, $2]"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn recursive() {
+    let file_path = "./tests/print/recursive.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "[[...]]", "{\"self\": {...}}", "<instance Node{next: <instance Node{...}>}>"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn to_string_prints_this() {
+    let file_path = "./tests/print/to_string_prints_this.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Loop(<instance Loop{}>)"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn to_string_returns_non_string() {
+    let file_path = "./tests/print/to_string_returns_non_string.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "42"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
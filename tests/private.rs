@@ -0,0 +1,95 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn field() {
+    let file_path = "./tests/private/field.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "2"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn method() {
+    let file_path = "./tests/private/method.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Hello, Lox!"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn closure() {
+    let file_path = "./tests/private/closure.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "7"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn public_unaffected() {
+    let file_path = "./tests/private/public_unaffected.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "4"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn outside_class() {
+    let file_path = "./tests/private/outside_class.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Private member '#value' can only be accessed through 'this'"));
+}
+
+#[test]
+fn other_instance() {
+    let file_path = "./tests/private/other_instance.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Private member '#balance' can only be accessed through 'this'"));
+}
+
+#[test]
+fn trait_method() {
+    let file_path = "./tests/private/trait_method.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can't access private member '#secret' in trait methods"));
+}
+
+#[test]
+fn static_field() {
+    let file_path = "./tests/private/static_field.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Private member '#next' can only be accessed through 'this'"));
+}
@@ -0,0 +1,201 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn undefined() {
+    let file_path = "./tests/strict/undefined.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Undefined variable 'cont', did you mean 'count'.\n   line 4 |     print cont;"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn no_suggestion() {
+    let file_path = "./tests/strict/no_suggestion.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Undefined variable 'zebra'.\n   line 4 |     print zebra;"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn defined_later() {
+    let file_path = "./tests/strict/defined_later.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "42", "true"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn read_before_declaration() {
+    let file_path = "./tests/strict/read_before_declaration.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Can't read 'value' before it is assigned.\n   line 2 | print value;"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn unassigned() {
+    let file_path = "./tests/strict/unassigned.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Can't read 'a' before it is assigned.\n   line 5 |     return a;"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn assigned_in_branches() {
+    let file_path = "./tests/strict/assigned_in_branches.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "-1", "0"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn loop_assignment() {
+    let file_path = "./tests/strict/loop_assignment.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Can't read 'total' before it is assigned.\n   line 4 | print total;"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn without_pragma() {
+    let file_path = "./tests/strict/without_pragma.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Nil", "done"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn pragma_not_first() {
+    let file_path = "./tests/strict/pragma_not_first.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "first"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn assign_undefined() {
+    let file_path = "./tests/strict/assign_undefined.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Undefined variable 'totl', did you mean 'total'.\n   line 2 | fun reset() { totl = 0; }"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn strict_flag() {
+    let file_path = "./tests/strict/pragma_not_first.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.set_strict(true);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Undefined variable 'missing'.\n   line 3 | if (false) print missing;"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn globals_of_earlier_runs() {
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(true, &printer);
+    lox_runner.set_strict(true);
+    lox_runner.run("var a = 1; var b;");
+    lox_runner.run("print a;");
+    lox_runner.run("print b;");
+    let result = vec![
+        "1", "Error: Can't read 'b' before it is assigned."
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn assigned_before_break() {
+    let file_path = "./tests/strict/assigned_before_break.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "1", "2"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn break_before_assignment() {
+    let file_path = "./tests/strict/break_before_assignment.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Can't read 'y' before it is assigned.\n   line 8 | print y;"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn eval_source() {
+    let file_path = "./tests/strict/eval_source.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.set_strict(true);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "3", "Error: Invalid source at line 1: Undefined variable 'missing'.\n   line 2 | eval(\"if (false) print missing;\");"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
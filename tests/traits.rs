@@ -0,0 +1,108 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn compose() {
+    let file_path = "./tests/traits/compose.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "true", "false", "Money(5)", "<trait Comparable>"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn class_overrides_trait() {
+    let file_path = "./tests/traits/class_overrides_trait.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "class"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn conflict() {
+    let file_path = "./tests/traits/conflict.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Method 'name' of class 'C' is provided by both traits 'A' and 'B'"));
+}
+
+#[test]
+fn conflict_resolved_by_class() {
+    let file_path = "./tests/traits/conflict_resolved_by_class.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "C"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn missing_required() {
+    let file_path = "./tests/traits/missing_required.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Class 'Money' must define method 'compare' required by trait 'Comparable'"));
+}
+
+#[test]
+fn required_arity() {
+    let file_path = "./tests/traits/required_arity.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Method 'compare' of class 'Money' must take 1 parameters as required by trait 'Comparable'"));
+}
+
+#[test]
+fn required_by_other_trait() {
+    let file_path = "./tests/traits/required_by_other_trait.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "fixed"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn not_a_trait() {
+    let file_path = "./tests/traits/not_a_trait.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: 'Base' is not a trait"));
+}
+
+#[test]
+fn several_conflicts() {
+    let file_path = "./tests/traits/several_conflicts.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Method 'describe' of class 'Duck' is provided by both traits 'Walker' and 'Swimmer'.\n   line 13 | class Duck with Walker, Swimmer {}", "Note: Define 'describe' in class 'Duck' to resolve the conflict.\n   line 7 | trait Swimmer {"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
@@ -0,0 +1,117 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn print() {
+    let file_path = "./tests/tuple/print.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "(1, \"x\")", "(\"a\", (true, Nil), 2.5)", "(\"single\",)", "(1, 2)"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn equality() {
+    let file_path = "./tests/tuple/equality.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "true", "true", "false", "true", "false"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn multiple_return() {
+    let file_path = "./tests/tuple/multiple_return.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "3", "1", "(3, 0)"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn swap() {
+    let file_path = "./tests/tuple/swap.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "2", "1"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn destructure_mismatch() {
+    let file_path = "./tests/tuple/destructure_mismatch.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Too many values to destructure into '(a, b)', expected 2 received 3."));
+}
+
+#[test]
+fn single_element_pattern() {
+    let file_path = "./tests/tuple/single_element_pattern.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "5", "6", "7", "8", "9", "tuple", "value"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn map_keys() {
+    let file_path = "./tests/tuple/map_keys.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "point", "start", "3", "true", "false", "{(0, 0): \"start\", (1, 2): \"point\", (2, 1): \"other\"}"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn empty_pattern() {
+    let file_path = "./tests/tuple/empty_pattern.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Expected pattern in '()', as there are no empty tuples.\n   line 1 | var () = 1;"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn empty_match_pattern() {
+    let file_path = "./tests/tuple/empty_match_pattern.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Expected pattern in '()', as there are no empty tuples.\n   line 2 |   () => print \"empty\","
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
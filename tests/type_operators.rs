@@ -0,0 +1,72 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn type_of() {
+    let file_path = "./tests/type_operators/type_of.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "number", "string", "bool", "nil", "function", "function", "class", "instance", "list", "map", "tuple", "enum", "variant", "trait", "true", "string"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn instance_of() {
+    let file_path = "./tests/type_operators/instance_of.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "true", "false", "false", "true", "false", "false", "true"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn is_not_class() {
+    let file_path = "./tests/type_operators/is_not_class.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Right operand of 'is' must be a class or enum"));
+}
+
+#[test]
+fn membership() {
+    let file_path = "./tests/type_operators/membership.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "true", "true", "false", "true", "true", "true", "false", "true", "true", "false", "true", "false", "true", "false", "true"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn in_not_collection() {
+    let file_path = "./tests/type_operators/in_not_collection.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Right operand of 'in' must be a map, list, tuple, string or instance"));
+}
+
+#[test]
+fn in_string_key() {
+    let file_path = "./tests/type_operators/in_string_key.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Left operand of 'in' must be a string"));
+}
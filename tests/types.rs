@@ -0,0 +1,211 @@
+use std::fs;
+
+use rlox::lox::{printer::TestPrinter, Lox};
+
+#[test]
+fn annotated() {
+    let file_path = "./tests/types/annotated.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "3", "Hello, b", "6", "3"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn dynamic() {
+    let file_path = "./tests/types/dynamic.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "2", "now a string"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn classes() {
+    let file_path = "./tests/types/classes.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "2", "Color.Red"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn var_mismatch() {
+    let file_path = "./tests/types/var_mismatch.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can't initialize 'a' of type 'Number' with value of type 'String'"));
+}
+
+#[test]
+fn assign_mismatch() {
+    let file_path = "./tests/types/assign_mismatch.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can't assign value of type 'String' to 'a' of type 'Number'"));
+}
+
+#[test]
+fn argument_mismatch() {
+    let file_path = "./tests/types/argument_mismatch.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Argument 1 of type 'Number' doesn't match parameter of type 'String'"));
+}
+
+#[test]
+fn return_mismatch() {
+    let file_path = "./tests/types/return_mismatch.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can't return value of type 'Number' from function returning 'Bool'"));
+}
+
+#[test]
+fn list_element_mismatch() {
+    let file_path = "./tests/types/list_element_mismatch.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can't initialize 'names' of type 'List<String>' with value of type 'List<Number>'"));
+}
+
+#[test]
+fn set_index_mismatch() {
+    let file_path = "./tests/types/set_index_mismatch.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can't store value of type 'String' in 'List<Number>'"));
+}
+
+#[test]
+fn operands() {
+    let file_path = "./tests/types/operands.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Operands of '-' must be numbers, found 'String' and 'Number'"));
+}
+
+#[test]
+fn unknown_type() {
+    let file_path = "./tests/types/unknown_type.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Unknown type 'Pointt'"));
+}
+
+#[test]
+fn uninitialized() {
+    let file_path = "./tests/types/uninitialized.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Variable 'count' of type 'Number' must be initialized"));
+}
+
+#[test]
+fn class_mismatch() {
+    let file_path = "./tests/types/class_mismatch.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can't initialize 'p' of type 'Point' with value of type 'Circle'"));
+}
+
+#[test]
+fn arity() {
+    let file_path = "./tests/types/arity.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Expected 2 arguments, received 1"));
+}
+
+#[test]
+fn type_arguments() {
+    let file_path = "./tests/types/type_arguments.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Type 'Number' doesn't take type arguments"));
+}
+
+#[test]
+fn checked_before_run() {
+    let file_path = "./tests/types/checked_before_run.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    assert!(printer.result.borrow()[0].starts_with("Error: Can't initialize 'a' of type 'Bool'"));
+}
+
+#[test]
+fn mixed_list_element() {
+    let file_path = "./tests/types/mixed_list_element.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: List element of type 'String' doesn't match declared type 'Number'.\n   line 1 | var l: List<Number> = [1, \"a\"]; // Error at '[': List element of type 'String' doesn't match declared type 'Number'."
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn mixed_map_value() {
+    let file_path = "./tests/types/mixed_map_value.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "Error: Map value of type 'Bool' doesn't match declared type 'Number'.\n   line 2 |   return {\"a\": 1, \"b\": true};"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}
+
+#[test]
+fn mixed_elements_dynamic() {
+    let file_path = "./tests/types/mixed_elements_dynamic.lox";
+    let source_code = fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read file: {}", file_path));
+    let printer = TestPrinter::default();
+    let mut lox_runner = Lox::new(false, &printer);
+    lox_runner.run(&source_code);
+    let result = vec![
+        "[1, \"a\"]", "[[1, 2], [3]]", "[1, Nil, 2]"
+    ];
+    assert_eq!(*printer.result.borrow(), result);
+}